@@ -11,6 +11,7 @@ use crate::{
             IMAGE_METADATA_DESCRIPTION_KEY, IMAGE_METADATA_ENTRY_POINT_KEY,
             IMAGE_METADATA_LOCATION_KEY,
         },
+        port_info::PublishedPort,
     },
     scell::{
         SCell,
@@ -21,6 +22,7 @@ use crate::{
 
 pub const CONTAINER_METADATA_IMAGE_ID_KEY: &str = "scell-image-id";
 pub const CONTAINER_METADATA_DESCRIPTION_KEY: &str = "scell-container-description";
+pub const CONTAINER_METADATA_SHELL_KEY: &str = "scell-shell";
 const SERVICE_NAME_DELIMETER: char = '.';
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -35,6 +37,10 @@ pub struct SCellContainerInfo {
     pub image_desc: Option<yaml_serde::Value>,
     pub container_desc: Option<yaml_serde::Value>,
     pub created_at: Option<DateTime<Utc>>,
+    pub ports: Vec<PublishedPort>,
+    /// The shell binary path, the session is started with. Recorded only for the main,
+    /// not service, containers.
+    pub shell: Option<String>,
     // A Docker image id, not a [`SCellId`]
     pub docker_image_id: String,
 }
@@ -51,7 +57,37 @@ impl SCellContainerInfo {
     }
 }
 
-#[derive(Debug, Clone, Default, Copy, PartialEq, PartialOrd, Eq, Ord, Hash)]
+/// Change of the 'Shell-Cell' container, reported by the Docker events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SCellContainerEvent {
+    /// The container with the name has changed its status.
+    Status(String, Status),
+    /// Some container was created or removed, so the list must be re-fetched.
+    Changed,
+}
+
+impl SCellContainerEvent {
+    /// Docker container event actions, which are mapped to the [`SCellContainerEvent`].
+    /// `stop` and `kill` are not listed, as they are always followed by `die`.
+    pub const ACTIONS: &[&str] = &[
+        "create", "destroy", "start", "restart", "unpause", "pause", "die",
+    ];
+
+    pub fn from_action(
+        container_name: String,
+        action: &str,
+    ) -> Option<Self> {
+        match action {
+            "create" | "destroy" => Some(Self::Changed),
+            "start" | "restart" | "unpause" => Some(Self::Status(container_name, Status::Running)),
+            "pause" => Some(Self::Status(container_name, Status::Paused)),
+            "die" => Some(Self::Status(container_name, Status::Exited)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Copy, PartialEq, PartialOrd, Eq, Ord, Hash, clap::ValueEnum)]
 pub enum Status {
     #[default]
     Empty,
@@ -162,6 +198,17 @@ impl TryFrom<bollard::models::ContainerSummary> for SCellContainerInfo {
             .and_then(|v| v.get(CONTAINER_METADATA_IMAGE_ID_KEY).map(|s| s.parse()))
             .transpose()?;
 
+        let shell = value
+            .labels
+            .as_ref()
+            .and_then(|v| v.get(CONTAINER_METADATA_SHELL_KEY).cloned());
+
+        let ports = value
+            .ports
+            .as_deref()
+            .map(PublishedPort::from_summary)
+            .unwrap_or_default();
+
         let docker_image_id = value
             .image_id
             .context("'Shell-Cell' container must have a corresponding Docker/Podman image ID")?;
@@ -198,7 +245,34 @@ impl TryFrom<bollard::models::ContainerSummary> for SCellContainerInfo {
             image_desc,
             container_desc,
             created_at,
+            ports,
+            shell,
             docker_image_id,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("create", Some(SCellContainerEvent::Changed) ; "create")]
+    #[test_case("destroy", Some(SCellContainerEvent::Changed) ; "destroy")]
+    #[test_case("start", Some(SCellContainerEvent::Status("scell-a".to_string(), Status::Running)) ; "start")]
+    #[test_case("unpause", Some(SCellContainerEvent::Status("scell-a".to_string(), Status::Running)) ; "unpause")]
+    #[test_case("pause", Some(SCellContainerEvent::Status("scell-a".to_string(), Status::Paused)) ; "pause")]
+    #[test_case("die", Some(SCellContainerEvent::Status("scell-a".to_string(), Status::Exited)) ; "die")]
+    #[test_case("exec_start: /bin/bash", None ; "exec start")]
+    #[allow(clippy::needless_pass_by_value)]
+    fn from_action_test(
+        action: &str,
+        expected: Option<SCellContainerEvent>,
+    ) {
+        assert_eq!(
+            SCellContainerEvent::from_action("scell-a".to_string(), action),
+            expected
+        );
+    }
+}
@@ -1,14 +1,17 @@
-use std::{collections::HashMap, pin::Pin};
+use std::{collections::HashMap, pin::Pin, time::Duration};
 
 use bollard::{
     Docker, body_full,
     container::LogOutput,
     exec::{ResizeExecOptions, StartExecOptions, StartExecResults},
-    models::{ContainerCreateBody, ContainerSummary, ExecConfig, ImageSummary},
+    models::{
+        ContainerCreateBody, ContainerInspectResponse, ContainerStatsResponse, ContainerSummary,
+        ExecConfig, ImageSummary,
+    },
     query_parameters::{
-        BuildImageOptionsBuilder, CreateContainerOptions, CreateImageOptions,
-        ListContainersOptionsBuilder, ListImagesOptionsBuilder, RemoveContainerOptionsBuilder,
-        RemoveImageOptionsBuilder,
+        BuildImageOptionsBuilder, CreateContainerOptions, CreateImageOptions, EventsOptions,
+        ListContainersOptionsBuilder, ListImagesOptionsBuilder, LogsOptions,
+        RemoveContainerOptionsBuilder, RemoveImageOptionsBuilder, StatsOptions,
     },
 };
 use bytes::Bytes;
@@ -143,6 +146,14 @@ pub async fn remove_image(
     Ok(())
 }
 
+pub async fn inspect_container(
+    docker: &Docker,
+    container_name: &str,
+) -> color_eyre::Result<ContainerInspectResponse> {
+    let res = docker.inspect_container(container_name, None).await?;
+    Ok(res)
+}
+
 pub async fn list_all_containers(docker: &Docker) -> color_eyre::Result<Vec<ContainerSummary>> {
     let res = docker
         .list_containers(Some(ListContainersOptionsBuilder::new().all(true).build()))
@@ -157,6 +168,79 @@ pub async fn list_all_images(docker: &Docker) -> color_eyre::Result<Vec<ImageSum
     Ok(res)
 }
 
+/// Streams the `actions` events of the containers with the `label`, as the container
+/// name and the action pairs.
+pub fn container_events(
+    docker: &Docker,
+    label: &str,
+    actions: &[&str],
+) -> impl Stream<Item = color_eyre::Result<(String, String)>> + Send + use<> {
+    let filters = [
+        ("type".to_string(), vec!["container".to_string()]),
+        ("label".to_string(), vec![label.to_string()]),
+        (
+            "event".to_string(),
+            actions.iter().map(ToString::to_string).collect(),
+        ),
+    ]
+    .into_iter()
+    .collect();
+    docker
+        .events(Some(EventsOptions {
+            filters: Some(filters),
+            ..Default::default()
+        }))
+        .filter_map(|event| {
+            async move {
+                match event {
+                    Ok(event) => {
+                        let action = event.action?;
+                        let name = event.actor?.attributes?.remove("name")?;
+                        Some(Ok((name, action)))
+                    },
+                    Err(e) => Some(Err(e.into())),
+                }
+            }
+        })
+}
+
+/// Streams the stdout and stderr of the container's main process, starting from the
+/// last `tail` lines and following the new output.
+pub fn container_logs(
+    docker: &Docker,
+    container_name: &str,
+    tail: usize,
+) -> impl Stream<Item = color_eyre::Result<Bytes>> + Send + use<> {
+    docker
+        .logs(
+            container_name,
+            Some(LogsOptions {
+                follow: true,
+                stdout: true,
+                stderr: true,
+                tail: tail.to_string(),
+                ..Default::default()
+            }),
+        )
+        .map(|output| Ok(output?.into_bytes()))
+}
+
+/// Streams the resource usage samples of the container, one per second.
+pub fn container_stats(
+    docker: &Docker,
+    container_name: &str,
+) -> impl Stream<Item = color_eyre::Result<ContainerStatsResponse>> + Send + use<> {
+    docker
+        .stats(
+            container_name,
+            Some(StatsOptions {
+                stream: true,
+                one_shot: false,
+            }),
+        )
+        .map(|stats| Ok(stats?))
+}
+
 type Output = Pin<Box<dyn Stream<Item = Result<LogOutput, bollard::errors::Error>> + Send>>;
 type Input = Pin<Box<dyn AsyncWrite + Send>>;
 type PtySessionId = String;
@@ -195,6 +279,26 @@ pub async fn container_iteractive_exec(
     Ok((exec_id, output, input))
 }
 
+/// Returns the exit code of the exec, waiting for a short while for it to finish,
+/// as the exec could still be running right after its output stream ended.
+/// Returns `None` if the exec is still running.
+pub async fn container_exec_exit_code(
+    docker: &Docker,
+    exec_id: &str,
+) -> color_eyre::Result<Option<i64>> {
+    const ATTEMPTS: usize = 10;
+    const DELAY: Duration = Duration::from_millis(50);
+
+    for _ in 0..ATTEMPTS {
+        let info = docker.inspect_exec(exec_id).await?;
+        if !info.running.unwrap_or_default() {
+            return Ok(info.exit_code);
+        }
+        tokio::time::sleep(DELAY).await;
+    }
+    Ok(None)
+}
+
 pub async fn container_resize_exec(
     docker: &Docker,
     exec_id: &str,
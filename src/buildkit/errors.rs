@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+use crate::scell::{
+    name::SCellId,
+    types::{name::TargetName, target::config::forward::ForwardItem},
+};
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error(
+    "'Shell-Cell' container '{0}' for target '{1}' does not exist, start it first by running `scell`"
+)]
+pub struct ContainerNotFound(pub SCellId, pub TargetName);
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error(
+    "'Shell-Cell' container '{0}' for target '{1}' is not running, start it first by running `scell`"
+)]
+pub struct ContainerNotRunning(pub SCellId, pub TargetName);
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error(
+    "Cannot attach to the 'Shell-Cell' service container '{0}', only the main 'Shell-Cell' containers have a shell session"
+)]
+pub struct ServiceContainerAttach(pub String);
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error(
+    "Cannot determine the shell of the 'Shell-Cell' container '{0}', start it again by running `scell`"
+)]
+pub struct UnknownContainerShell(pub SCellId);
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error(
+    "Cannot start the stopped 'Shell-Cell' container '{0}', its blueprint is missing or has changed, start it again by running `scell`"
+)]
+pub struct StoppedContainerAttach(pub SCellId);
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error(
+    "Host port '{0}' of the target '{1}' is already allocated by the 'Shell-Cell' container '{2}' ({3})"
+)]
+pub struct PortAllocatedByCell(pub String, pub TargetName, pub SCellId, pub String);
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error("Host port '{0}' of the target '{1}' is already in use by another process on the host")]
+pub struct PortAlreadyInUse(pub String, pub TargetName);
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error(
+    "Cannot forward 'ssh-agent', SSH agent is not running on the host ('SSH_AUTH_SOCK' is not set or points to a missing socket)"
+)]
+pub struct SshAgentNotRunning;
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error("Cannot forward '{0}', the host file '{1}' does not exist")]
+pub struct ForwardedFileNotFound(pub ForwardItem, pub PathBuf);
@@ -0,0 +1,183 @@
+use std::path::{Path, PathBuf};
+
+use color_eyre::eyre::ContextCompat;
+
+use crate::{
+    buildkit::errors::{ForwardedFileNotFound, SshAgentNotRunning},
+    error::{OptionUserError, UserError},
+    scell::types::target::config::forward::{ForwardItem, ForwardStmt},
+};
+
+const SSH_AUTH_SOCK_ENV: &str = "SSH_AUTH_SOCK";
+const CONTAINER_SSH_AUTH_SOCK: &str = "/run/scell/ssh-agent.sock";
+const CONTAINER_GITCONFIG: &str = "/etc/gitconfig";
+const CONTAINER_KNOWN_HOSTS: &str = "/etc/ssh/ssh_known_hosts";
+
+/// Bind mounts and environment variables, required to forward host facilities into
+/// the container.
+#[derive(Debug, Default)]
+pub struct ForwardConfig {
+    pub binds: Vec<String>,
+    pub env: Vec<String>,
+}
+
+/// The host environment, the forwarded items are resolved against.
+struct HostEnv {
+    home: Option<PathBuf>,
+    ssh_auth_sock: Option<PathBuf>,
+}
+
+impl HostEnv {
+    fn current() -> Self {
+        Self {
+            home: dirs::home_dir(),
+            ssh_auth_sock: std::env::var_os(SSH_AUTH_SOCK_ENV).map(PathBuf::from),
+        }
+    }
+
+    fn home(&self) -> color_eyre::Result<&Path> {
+        self.home
+            .as_deref()
+            .context("Current platform does not have a home directory")
+    }
+}
+
+impl ForwardConfig {
+    /// Resolves forwarded items against the current host environment.
+    /// It is done on the container creation, not on the blueprint compilation,
+    /// so the host specific paths (e.g. the SSH agent socket) do not affect the
+    /// container's ID.
+    pub fn resolve(forward: &ForwardStmt) -> color_eyre::Result<Self> {
+        Self::resolve_in(forward, &HostEnv::current())
+    }
+
+    fn resolve_in(
+        forward: &ForwardStmt,
+        host: &HostEnv,
+    ) -> color_eyre::Result<Self> {
+        let mut res = Self::default();
+        for item in &forward.0 {
+            match item {
+                ForwardItem::SshAgent => {
+                    let sock = host
+                        .ssh_auth_sock
+                        .as_ref()
+                        .filter(|p| p.exists())
+                        .user_err(SshAgentNotRunning)?;
+                    res.binds
+                        .push(format!("{}:{CONTAINER_SSH_AUTH_SOCK}", sock.display()));
+                    res.env
+                        .push(format!("{SSH_AUTH_SOCK_ENV}={CONTAINER_SSH_AUTH_SOCK}"));
+                },
+                ForwardItem::GitConfig => {
+                    let home = host.home()?;
+                    let candidates = [
+                        home.join(".gitconfig"),
+                        home.join(".config").join("git").join("config"),
+                    ];
+                    let Some(path) = candidates.iter().find(|p| p.exists()) else {
+                        return UserError::bail(ForwardedFileNotFound(
+                            *item,
+                            home.join(".gitconfig"),
+                        ))?;
+                    };
+                    res.binds.push(read_only_bind(path, CONTAINER_GITCONFIG));
+                },
+                ForwardItem::KnownHosts => {
+                    let path = host.home()?.join(".ssh").join("known_hosts");
+                    if !path.exists() {
+                        return UserError::bail(ForwardedFileNotFound(*item, path))?;
+                    }
+                    res.binds.push(read_only_bind(&path, CONTAINER_KNOWN_HOSTS));
+                },
+            }
+        }
+        Ok(res)
+    }
+
+    /// Describes forwarded items with the host environment variables (e.g.
+    /// `${SSH_AUTH_SOCK}`), substituted by Docker Compose, instead of the resolved host
+    /// paths. The host is not checked, so the exported project works on any host.
+    ///
+    /// Docker Compose could not fall back between the files, so only `~/.gitconfig` is
+    /// forwarded, not the XDG one. An unset `SSH_AUTH_SOCK` fails the substitution.
+    pub fn portable(forward: &ForwardStmt) -> Self {
+        let mut res = Self::default();
+        for item in &forward.0 {
+            match item {
+                ForwardItem::SshAgent => {
+                    res.binds.push(format!(
+                        "${{{SSH_AUTH_SOCK_ENV}:?the SSH agent is not running}}:{CONTAINER_SSH_AUTH_SOCK}"
+                    ));
+                    res.env
+                        .push(format!("{SSH_AUTH_SOCK_ENV}={CONTAINER_SSH_AUTH_SOCK}"));
+                },
+                ForwardItem::GitConfig => {
+                    res.binds.push(read_only_bind(
+                        Path::new("${HOME}/.gitconfig"),
+                        CONTAINER_GITCONFIG,
+                    ));
+                },
+                ForwardItem::KnownHosts => {
+                    res.binds.push(read_only_bind(
+                        Path::new("${HOME}/.ssh/known_hosts"),
+                        CONTAINER_KNOWN_HOSTS,
+                    ));
+                },
+            }
+        }
+        res
+    }
+}
+
+fn read_only_bind(
+    host: &Path,
+    container: &str,
+) -> String {
+    format!("{}:{container}:ro", host.display())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(&[ForwardItem::SshAgent], &["agent.sock"], Some("agent.sock") => Some(vec!["$TMP/agent.sock:/run/scell/ssh-agent.sock".to_string()]) ; "ssh agent")]
+    #[test_case(&[ForwardItem::SshAgent], &[], Some("agent.sock") => None ; "ssh agent socket not found")]
+    #[test_case(&[ForwardItem::SshAgent], &[], None => None ; "ssh agent not running")]
+    #[test_case(&[ForwardItem::GitConfig], &[".gitconfig", ".config/git/config"], None => Some(vec!["$TMP/.gitconfig:/etc/gitconfig:ro".to_string()]) ; "gitconfig")]
+    #[test_case(&[ForwardItem::GitConfig], &[".config/git/config"], None => Some(vec!["$TMP/.config/git/config:/etc/gitconfig:ro".to_string()]) ; "xdg gitconfig")]
+    #[test_case(&[ForwardItem::GitConfig], &[], None => None ; "gitconfig not found")]
+    #[test_case(&[ForwardItem::KnownHosts], &[".ssh/known_hosts"], None => Some(vec!["$TMP/.ssh/known_hosts:/etc/ssh/ssh_known_hosts:ro".to_string()]) ; "known hosts")]
+    #[test_case(&[ForwardItem::KnownHosts], &[], None => None ; "known hosts not found")]
+    fn resolve_test(
+        items: &[ForwardItem],
+        files: &[&str],
+        ssh_auth_sock: Option<&str>,
+    ) -> Option<Vec<String>> {
+        let tmp = std::env::temp_dir().join(format!("scell_{}", uuid::Uuid::now_v7()));
+        for file in files {
+            let path = tmp.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        let host = HostEnv {
+            home: Some(tmp.clone()),
+            ssh_auth_sock: ssh_auth_sock.map(|s| tmp.join(s)),
+        };
+        let res = ForwardConfig::resolve_in(&ForwardStmt(items.to_vec()), &host);
+        drop(std::fs::remove_dir_all(&tmp));
+
+        let res = res.ok()?;
+        if items.contains(&ForwardItem::SshAgent) {
+            assert_eq!(res.env, vec!["SSH_AUTH_SOCK=/run/scell/ssh-agent.sock"]);
+        }
+        Some(
+            res.binds
+                .iter()
+                .map(|b| b.replace(&tmp.display().to_string(), "$TMP"))
+                .collect(),
+        )
+    }
+}
@@ -2,7 +2,11 @@
 
 pub mod container_info;
 mod docker;
+pub mod errors;
+mod forward;
 pub mod image_info;
+pub mod port_info;
+pub mod stats_info;
 
 use std::collections::HashMap;
 
@@ -11,26 +15,41 @@ use bollard::{
     Docker,
     models::{ContainerCreateBody, HostConfig, PortBinding},
 };
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
 
 use crate::{
     buildkit::{
         container_info::{
-            CONTAINER_METADATA_DESCRIPTION_KEY, CONTAINER_METADATA_IMAGE_ID_KEY, SCellContainerInfo,
+            CONTAINER_METADATA_DESCRIPTION_KEY, CONTAINER_METADATA_IMAGE_ID_KEY,
+            CONTAINER_METADATA_SHELL_KEY, SCellContainerEvent, SCellContainerInfo, Status,
         },
         docker::{
-            build_image, container_iteractive_exec, container_resize_exec, list_all_containers,
-            list_all_images, pull_image, remove_container, remove_image, start_container,
-            stop_container,
+            build_image, container_events, container_exec_exit_code, container_iteractive_exec,
+            container_logs, container_resize_exec, container_stats, inspect_container,
+            list_all_containers, list_all_images, pull_image, remove_container, remove_image,
+            start_container, stop_container,
         },
+        errors::{
+            ContainerNotFound, ContainerNotRunning, PortAllocatedByCell, PortAlreadyInUse,
+            ServiceContainerAttach, StoppedContainerAttach, UnknownContainerShell,
+        },
+        forward::ForwardConfig,
         image_info::{
             IMAGE_METADATA_DESCRIPTION_KEY, IMAGE_METADATA_ENTRY_POINT_KEY,
             IMAGE_METADATA_LOCATION_KEY, SCellImageInfo,
         },
+        port_info::{PublishedPort, is_host_port_available},
+        stats_info::SCellContainerStats,
     },
-    error::WrapUserError,
+    error::{OptionUserError, Report, UserError, WrapUserError},
     pty::Pty,
     scell::{
-        SCell, container::SCellContainer, image::SCellImage, types::target::services::ServiceName,
+        SCell,
+        container::SCellContainer,
+        image::SCellImage,
+        name::SCellId,
+        types::{name::TargetName, target::services::ServiceName},
     },
 };
 
@@ -103,11 +122,23 @@ impl BuildKitD {
         &self,
         scell: &SCell,
     ) -> color_eyre::Result<()> {
+        self.check_ports(
+            &scell.container_id()?,
+            None,
+            scell.image().entry_point(),
+            scell.container(),
+        )
+        .await?;
         start_container(
             &self.docker,
             &SCellImageInfo::image_name(&scell.image().id()?),
             &SCellContainerInfo::container_name(&scell.container_id()?, None),
-            container_config(scell.image(), scell.container())?,
+            container_config(
+                scell.image(),
+                scell.container(),
+                Some(scell.shell()),
+                ForwardConfig::resolve(&scell.container().forward())?,
+            )?,
         )
         .await
         .mark_as_user_err()?;
@@ -121,17 +152,90 @@ impl BuildKitD {
         image: &SCellImage,
         container: &SCellContainer,
     ) -> color_eyre::Result<()> {
+        self.check_ports(&scell.container_id()?, Some(name), name, container)
+            .await?;
         start_container(
             &self.docker,
             &SCellImageInfo::image_name(&image.id()?),
             &SCellContainerInfo::container_name(&scell.container_id()?, Some(name)),
-            container_config(scell.image(), container)?,
+            container_config(
+                scell.image(),
+                container,
+                None,
+                ForwardConfig::resolve(&container.forward())?,
+            )?,
         )
         .await
         .mark_as_user_err()?;
         Ok(())
     }
 
+    /// Checks that every fixed host port of the container is not occupied by some other
+    /// running 'Shell-Cell' container or by any other process on the host, before the
+    /// container is created and started.
+    async fn check_ports(
+        &self,
+        id: &SCellId,
+        service_name: Option<&ServiceName>,
+        target: &TargetName,
+        container: &SCellContainer,
+    ) -> color_eyre::Result<()> {
+        let ports = container.ports();
+        if ports.0.is_empty() {
+            return Ok(());
+        }
+
+        let running: Vec<_> = self
+            .list_containers()
+            .await?
+            .into_iter()
+            .filter(|c| c.status == Status::Running)
+            .collect();
+        // The container is already running, so it already holds its own ports
+        if running
+            .iter()
+            .any(|c| &c.id == id && c.service_name.as_ref() == service_name)
+        {
+            return Ok(());
+        }
+
+        let mut report = Report::new();
+        for p in &ports.0 {
+            for (_, host_port) in p.expand() {
+                // A random host port or a host port range, Docker picks any available one
+                let Some(host_port) = host_port.and_then(|h| h.parse::<u16>().ok()) else {
+                    continue;
+                };
+                let port = format!("{host_port}/{}", p.protocol.as_str());
+                if let Some(other) = running.iter().find(|c| {
+                    c.ports
+                        .iter()
+                        .any(|o| o.occupies(p.host_ip.as_deref(), host_port, &p.protocol))
+                }) {
+                    report.add_error(UserError::wrap(PortAllocatedByCell(
+                        port,
+                        target.clone(),
+                        other.id.clone(),
+                        format!(
+                            "{}+{}",
+                            other
+                                .location
+                                .as_ref()
+                                .map_or_else(|| "<empty>".to_string(), |l| l.display().to_string()),
+                            other
+                                .target
+                                .as_ref()
+                                .map_or_else(|| "<empty>".to_string(), ToString::to_string)
+                        ),
+                    )));
+                } else if !is_host_port_available(p.host_ip.as_deref(), host_port, &p.protocol) {
+                    report.add_error(UserError::wrap(PortAlreadyInUse(port, target.clone())));
+                }
+            }
+        }
+        report.check()
+    }
+
     pub async fn stop_container(
         &self,
         container: &SCellContainerInfo,
@@ -172,6 +276,52 @@ impl BuildKitD {
             .collect())
     }
 
+    /// Streams the status changes of the 'Shell-Cell' containers, as they are created,
+    /// started, stopped or removed.
+    pub fn container_events(
+        &self
+    ) -> impl Stream<Item = color_eyre::Result<SCellContainerEvent>> + Send + use<> {
+        container_events(
+            &self.docker,
+            CONTAINER_METADATA_IMAGE_ID_KEY,
+            SCellContainerEvent::ACTIONS,
+        )
+        .filter_map(|event| {
+            async move {
+                match event {
+                    Ok((name, action)) => SCellContainerEvent::from_action(name, &action).map(Ok),
+                    Err(e) => Some(Err(e)),
+                }
+            }
+        })
+    }
+
+    /// Streams the output of the container's main process, starting from the last `tail`
+    /// lines and following the new output.
+    pub fn container_logs(
+        &self,
+        container: &SCellContainerInfo,
+        tail: usize,
+    ) -> impl Stream<Item = color_eyre::Result<Bytes>> + Send + use<> {
+        container_logs(
+            &self.docker,
+            &SCellContainerInfo::container_name(&container.id, container.service_name.as_ref()),
+            tail,
+        )
+    }
+
+    /// Streams the resource usage of the container, one sample per second.
+    pub fn container_stats(
+        &self,
+        container: &SCellContainerInfo,
+    ) -> impl Stream<Item = color_eyre::Result<SCellContainerStats>> + Send + use<> {
+        container_stats(
+            &self.docker,
+            &SCellContainerInfo::container_name(&container.id, container.service_name.as_ref()),
+        )
+        .map(|stats| Ok(stats?.into()))
+    }
+
     pub async fn list_images(&self) -> color_eyre::Result<Vec<SCellImageInfo>> {
         Ok(list_all_images(&self.docker)
             .await?
@@ -188,6 +338,56 @@ impl BuildKitD {
             .collect())
     }
 
+    /// Returns the host ports which were actually published by Docker for the running
+    /// 'Shell-Cell' container, including the randomly picked ones.
+    pub async fn container_ports(
+        &self,
+        scell: &SCell,
+    ) -> color_eyre::Result<Vec<PublishedPort>> {
+        let container_id = scell.container_id()?;
+        let info = match inspect_container(
+            &self.docker,
+            &SCellContainerInfo::container_name(&container_id, None),
+        )
+        .await
+        {
+            Ok(info) => info,
+            Err(e)
+                if matches!(
+                    e.downcast_ref::<bollard::errors::Error>(),
+                    Some(bollard::errors::Error::DockerResponseServerError {
+                        status_code: 404,
+                        ..
+                    })
+                ) =>
+            {
+                return UserError::bail(ContainerNotFound(
+                    container_id,
+                    scell.image().entry_point().clone(),
+                ))?;
+            },
+            Err(e) => return Err(e),
+        };
+
+        if !info
+            .state
+            .as_ref()
+            .and_then(|s| s.running)
+            .unwrap_or_default()
+        {
+            return UserError::bail(ContainerNotRunning(
+                container_id,
+                scell.image().entry_point().clone(),
+            ))?;
+        }
+
+        Ok(info
+            .network_settings
+            .and_then(|n| n.ports)
+            .map(|ports| PublishedPort::from_port_map(&ports))
+            .unwrap_or_default())
+    }
+
     pub async fn attach_to_shell(
         &self,
         scell: &SCell,
@@ -202,6 +402,59 @@ impl BuildKitD {
         Ok(Pty::new(session_id, output, input))
     }
 
+    /// Starts the already existing container, if it is not running, and attaches to its
+    /// shell. The shell is the one recorded on the container start or, for the containers
+    /// started by the older versions, the one from the blueprint, if the blueprint still
+    /// compiles into the same container.
+    ///
+    /// The stopped container is started the same way as by `scell`, with its services
+    /// and the host ports check, so its blueprint must still compile into the same
+    /// container.
+    pub async fn attach_to_container(
+        &self,
+        container: &SCellContainerInfo,
+    ) -> color_eyre::Result<Pty> {
+        let container_name =
+            SCellContainerInfo::container_name(&container.id, container.service_name.as_ref());
+        if container.service_name.is_some() {
+            UserError::bail(ServiceContainerAttach(container_name.clone()))?;
+        }
+        let running = container.status == Status::Running;
+        let blueprint = (container.shell.is_none() || !running)
+            .then(|| {
+                let location = container.location.as_ref()?;
+                let scell = SCell::compile(location, container.target.clone()).ok()?;
+                // The blueprint could have changed since the container was started
+                (scell.container_id().ok()? == container.id).then_some(scell)
+            })
+            .flatten();
+        let shell = container
+            .shell
+            .clone()
+            .or_else(|| blueprint.as_ref().map(|s| s.shell().to_string()))
+            .user_err(UnknownContainerShell(container.id.clone()))?;
+
+        if !running {
+            let blueprint = blueprint.user_err(StoppedContainerAttach(container.id.clone()))?;
+            for (name, s) in blueprint.services() {
+                self.start_service_container(&blueprint, name, &s.image, &s.container)
+                    .await?;
+            }
+            self.start_container(&blueprint).await?;
+        }
+        let (session_id, output, input) =
+            container_iteractive_exec(&self.docker, &container_name, true, vec![shell]).await?;
+        Ok(Pty::new(session_id, output, input))
+    }
+
+    /// Returns the exit code of the finished shell session, if it is known.
+    pub async fn shell_exit_code(
+        &self,
+        session_id: &str,
+    ) -> color_eyre::Result<Option<i64>> {
+        container_exec_exit_code(&self.docker, session_id).await
+    }
+
     pub async fn resize_shell(
         &self,
         session_id: &str,
@@ -212,37 +465,68 @@ impl BuildKitD {
     }
 }
 
+/// Returns the configurations, the 'Shell-Cell' container (`None`) and its services
+/// containers are created with. The forwarded host facilities are described with the
+/// host environment variables, see [`ForwardConfig::portable`].
+pub fn containers_config(
+    scell: &SCell
+) -> color_eyre::Result<Vec<(Option<&ServiceName>, ContainerCreateBody)>> {
+    let main = container_config(
+        scell.image(),
+        scell.container(),
+        Some(scell.shell()),
+        ForwardConfig::portable(&scell.container().forward()),
+    )?;
+    let services = scell
+        .services()
+        .map(|(name, s)| {
+            Ok((
+                Some(name),
+                container_config(
+                    scell.image(),
+                    &s.container,
+                    None,
+                    ForwardConfig::portable(&s.container.forward()),
+                )?,
+            ))
+        })
+        .collect::<color_eyre::Result<Vec<_>>>()?;
+    Ok(std::iter::once((None, main)).chain(services).collect())
+}
+
 fn container_config(
     image: &SCellImage,
     container: &SCellContainer,
+    shell: Option<&str>,
+    forward: ForwardConfig,
 ) -> color_eyre::Result<ContainerCreateBody> {
     let binds: Vec<String> = container
         .mounts()
         .0
         .iter()
         .map(|m| format!("{}:{}", m.host.display(), m.container.display()))
+        .chain(forward.binds)
         .collect();
 
-    let ports = container.ports();
-
-    let exposed_ports: Vec<String> = ports
-        .0
-        .iter()
-        .map(|p| format!("{}/{}", p.container_port, p.protocol.as_str()))
-        .collect();
-
-    let port_bindings: HashMap<String, Option<Vec<PortBinding>>> = ports
-        .0
-        .into_iter()
-        .map(|p| {
-            let key = format!("{}/{}", p.container_port, p.protocol.as_str());
-            let binding = PortBinding {
-                host_ip: p.host_ip,
-                host_port: Some(p.host_port),
-            };
-            (key, Some(vec![binding]))
-        })
-        .collect();
+    let mut exposed_ports: Vec<String> = Vec::new();
+    let mut port_bindings: HashMap<String, Option<Vec<PortBinding>>> = HashMap::new();
+    for p in container.ports().0 {
+        for (container_port, host_port) in p.expand() {
+            let key = format!("{container_port}/{}", p.protocol.as_str());
+            if !exposed_ports.contains(&key) {
+                exposed_ports.push(key.clone());
+            }
+            // An empty `host_port` lets Docker pick a random available host port
+            port_bindings
+                .entry(key)
+                .or_default()
+                .get_or_insert_with(Vec::new)
+                .push(PortBinding {
+                    host_ip: p.host_ip.clone(),
+                    host_port,
+                });
+        }
+    }
 
     Ok(ContainerCreateBody {
         host_config: Some(HostConfig {
@@ -251,7 +535,8 @@ fn container_config(
             ..Default::default()
         }),
         exposed_ports: (!exposed_ports.is_empty()).then_some(exposed_ports),
-        labels: Some(container_metadata(image, container)?),
+        env: (!forward.env.is_empty()).then_some(forward.env),
+        labels: Some(container_metadata(image, container, shell)?),
         ..Default::default()
     })
 }
@@ -278,6 +563,7 @@ fn image_metadata(image: &SCellImage) -> color_eyre::Result<HashMap<String, Stri
 fn container_metadata(
     image: &SCellImage,
     container: &SCellContainer,
+    shell: Option<&str>,
 ) -> color_eyre::Result<HashMap<String, String>> {
     Ok([
         (
@@ -290,6 +576,7 @@ fn container_metadata(
         ),
     ]
     .into_iter()
+    .chain(shell.map(|shell| (CONTAINER_METADATA_SHELL_KEY.to_string(), shell.to_string())))
     .collect())
 }
 
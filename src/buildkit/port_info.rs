@@ -0,0 +1,239 @@
+use std::{
+    fmt::Display,
+    net::{TcpListener, UdpSocket},
+};
+
+use bollard::models::{PortMap, PortSummary};
+
+use crate::scell::types::target::config::ports::PortProtocol;
+
+const DEFAULT_HOST_IP: &str = "0.0.0.0";
+/// Host IPs, which mean binding to all available host interfaces.
+const WILDCARD_HOST_IPS: [&str; 2] = ["0.0.0.0", "::"];
+
+/// A container port published on the host, as it was actually bound by Docker.
+///
+/// Unlike the `ports` statement of the blueprint, it always contains a concrete host
+/// port, even if the host port was omitted and picked by Docker randomly.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PublishedPort {
+    pub container_port: u16,
+    pub protocol: String,
+    pub host_ip: Option<String>,
+    pub host_port: u16,
+}
+
+impl PublishedPort {
+    /// Collects published ports from the Docker's container summary, skipping exposed but
+    /// not published ones.
+    pub fn from_summary(ports: &[PortSummary]) -> Vec<Self> {
+        let mut res: Vec<_> = ports
+            .iter()
+            .filter_map(|p| {
+                Some(Self {
+                    container_port: p.private_port,
+                    protocol: p.typ.map_or_else(|| "tcp".to_string(), |t| t.to_string()),
+                    host_ip: p.ip.clone().filter(|ip| !ip.is_empty()),
+                    host_port: p.public_port?,
+                })
+            })
+            .collect();
+        res.sort();
+        res.dedup();
+        res
+    }
+
+    /// Collects published ports from the Docker's container inspect network settings.
+    /// Each key has the `<port>/<protocol>` format e.g. `80/tcp`.
+    pub fn from_port_map(ports: &PortMap) -> Vec<Self> {
+        let mut res: Vec<_> = ports
+            .iter()
+            .filter_map(|(key, bindings)| {
+                let (container_port, protocol) = key.split_once('/').unwrap_or((key, "tcp"));
+                let container_port = container_port.parse().ok()?;
+                Some(bindings.iter().flatten().filter_map(move |b| {
+                    Some(Self {
+                        container_port,
+                        protocol: protocol.to_string(),
+                        host_ip: b.host_ip.clone().filter(|ip| !ip.is_empty()),
+                        host_port: b.host_port.as_ref()?.parse().ok()?,
+                    })
+                }))
+            })
+            .flatten()
+            .collect();
+        res.sort();
+        res.dedup();
+        res
+    }
+
+    /// Returns `true` if the published port occupies the provided host port.
+    /// A missing or a wildcard host IP overlaps with any other host IP.
+    pub fn occupies(
+        &self,
+        host_ip: Option<&str>,
+        host_port: u16,
+        protocol: &PortProtocol,
+    ) -> bool {
+        let is_wildcard = |ip: Option<&str>| ip.is_none_or(|ip| WILDCARD_HOST_IPS.contains(&ip));
+        self.host_port == host_port
+            && self.protocol == protocol.as_str()
+            && (is_wildcard(self.host_ip.as_deref())
+                || is_wildcard(host_ip)
+                || self.host_ip.as_deref() == host_ip)
+    }
+}
+
+/// Checks whether the provided host port is not occupied by any other process on the
+/// host, by trying to bind it.
+/// Any other failure except "address in use" (e.g. not enough permissions to bind a
+/// privileged port) is not treated as a conflict, leaving the final decision to Docker.
+pub fn is_host_port_available(
+    host_ip: Option<&str>,
+    host_port: u16,
+    protocol: &PortProtocol,
+) -> bool {
+    let addr = (host_ip.unwrap_or(DEFAULT_HOST_IP), host_port);
+    let res = match protocol {
+        PortProtocol::Tcp => TcpListener::bind(addr).map(drop),
+        PortProtocol::Udp => UdpSocket::bind(addr).map(drop),
+    };
+    !matches!(res, Err(e) if e.kind() == std::io::ErrorKind::AddrInUse)
+}
+
+impl Display for PublishedPort {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        write!(
+            f,
+            "{}/{} -> {}:{}",
+            self.container_port,
+            self.protocol,
+            self.host_ip.as_deref().unwrap_or(DEFAULT_HOST_IP),
+            self.host_port
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bollard::models::{PortBinding, PortSummaryTypeEnum};
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test]
+    fn from_port_map_test() {
+        let ports: PortMap = [
+            (
+                "80/tcp".to_string(),
+                Some(vec![
+                    PortBinding {
+                        host_ip: Some("0.0.0.0".to_string()),
+                        host_port: Some("49153".to_string()),
+                    },
+                    PortBinding {
+                        host_ip: Some("::".to_string()),
+                        host_port: Some("49153".to_string()),
+                    },
+                ]),
+            ),
+            // exposed, but not published
+            ("443/tcp".to_string(), None),
+            (
+                "6060/udp".to_string(),
+                Some(vec![PortBinding {
+                    host_ip: None,
+                    host_port: Some("6060".to_string()),
+                }]),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let res: Vec<_> = PublishedPort::from_port_map(&ports)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(res, vec![
+            "80/tcp -> 0.0.0.0:49153",
+            "80/tcp -> :::49153",
+            "6060/udp -> 0.0.0.0:6060",
+        ]);
+    }
+
+    #[test]
+    fn from_summary_test() {
+        let ports = [
+            PortSummary {
+                ip: Some("127.0.0.1".to_string()),
+                private_port: 9000,
+                public_port: Some(9000),
+                typ: Some(PortSummaryTypeEnum::TCP),
+            },
+            PortSummary {
+                ip: None,
+                private_port: 443,
+                public_port: None,
+                typ: Some(PortSummaryTypeEnum::TCP),
+            },
+        ];
+
+        let res: Vec<_> = PublishedPort::from_summary(&ports)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(res, vec!["9000/tcp -> 127.0.0.1:9000"]);
+    }
+
+    #[test_case(None, 8080, &PortProtocol::Tcp => true ; "same port without host ip")]
+    #[test_case(Some("127.0.0.1"), 8080, &PortProtocol::Tcp => true ; "wildcard published host ip")]
+    #[test_case(None, 8081, &PortProtocol::Tcp => false ; "different port")]
+    #[test_case(None, 8080, &PortProtocol::Udp => false ; "different protocol")]
+    fn occupies_wildcard_test(
+        host_ip: Option<&str>,
+        host_port: u16,
+        protocol: &PortProtocol,
+    ) -> bool {
+        PublishedPort {
+            container_port: 80,
+            protocol: "tcp".to_string(),
+            host_ip: Some("0.0.0.0".to_string()),
+            host_port: 8080,
+        }
+        .occupies(host_ip, host_port, protocol)
+    }
+
+    #[test_case(Some("127.0.0.1") => true ; "same host ip")]
+    #[test_case(Some("0.0.0.0") => true ; "wildcard host ip")]
+    #[test_case(None => true ; "no host ip")]
+    #[test_case(Some("192.168.0.10") => false ; "different host ip")]
+    fn occupies_specific_ip_test(host_ip: Option<&str>) -> bool {
+        PublishedPort {
+            container_port: 80,
+            protocol: "tcp".to_string(),
+            host_ip: Some("127.0.0.1".to_string()),
+            host_port: 8080,
+        }
+        .occupies(host_ip, 8080, &PortProtocol::Tcp)
+    }
+
+    #[test]
+    fn is_host_port_available_test() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(!is_host_port_available(
+            Some("127.0.0.1"),
+            port,
+            &PortProtocol::Tcp
+        ));
+        drop(listener);
+        assert!(is_host_port_available(
+            Some("127.0.0.1"),
+            port,
+            &PortProtocol::Tcp
+        ));
+    }
+}
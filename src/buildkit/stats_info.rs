@@ -0,0 +1,159 @@
+use bollard::models::ContainerStatsResponse;
+
+/// Resource usage of the running 'Shell-Cell' container, as it is reported by Docker.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SCellContainerStats {
+    /// CPU usage since the previous sample, where 100% is one fully loaded CPU.
+    pub cpu_percent: f64,
+    /// Memory usage, excluding the page cache, as it is done by `docker stats`.
+    pub memory_usage: u64,
+    pub memory_limit: u64,
+    /// Total received bytes over all networks.
+    pub net_rx: u64,
+    /// Total transmitted bytes over all networks.
+    pub net_tx: u64,
+    pub pids: Option<u64>,
+}
+
+impl From<ContainerStatsResponse> for SCellContainerStats {
+    fn from(value: ContainerStatsResponse) -> Self {
+        let memory_usage = value.memory_stats.as_ref().map_or(0, |m| {
+            let cache = m
+                .stats
+                .as_ref()
+                .and_then(|s| {
+                    // cgroup v2 and cgroup v1 respectively
+                    s.get("inactive_file")
+                        .or_else(|| s.get("total_inactive_file"))
+                })
+                .copied()
+                .unwrap_or_default();
+            m.usage.unwrap_or_default().saturating_sub(cache)
+        });
+        let (net_rx, net_tx) = value
+            .networks
+            .iter()
+            .flat_map(|networks| networks.values())
+            .fold((0_u64, 0_u64), |(rx, tx), n| {
+                (
+                    rx.saturating_add(n.rx_bytes.unwrap_or_default()),
+                    tx.saturating_add(n.tx_bytes.unwrap_or_default()),
+                )
+            });
+        Self {
+            cpu_percent: cpu_percent(&value),
+            memory_usage,
+            memory_limit: value
+                .memory_stats
+                .as_ref()
+                .and_then(|m| m.limit)
+                .unwrap_or_default(),
+            net_rx,
+            net_tx,
+            pids: value.pids_stats.and_then(|p| p.current),
+        }
+    }
+}
+
+/// Calculates the CPU usage between the current and the previous samples, the same way
+/// as `docker stats` does.
+#[allow(clippy::cast_precision_loss)]
+fn cpu_percent(value: &ContainerStatsResponse) -> f64 {
+    let total = |stats: Option<&bollard::models::ContainerCpuStats>| {
+        (
+            stats
+                .and_then(|s| s.cpu_usage.as_ref())
+                .and_then(|u| u.total_usage)
+                .unwrap_or_default(),
+            stats.and_then(|s| s.system_cpu_usage).unwrap_or_default(),
+        )
+    };
+    let (cpu, system) = total(value.cpu_stats.as_ref());
+    let (pre_cpu, pre_system) = total(value.precpu_stats.as_ref());
+    let cpu_delta = cpu.saturating_sub(pre_cpu);
+    let system_delta = system.saturating_sub(pre_system);
+    if cpu_delta == 0 || system_delta == 0 {
+        return 0.0;
+    }
+    let online_cpus = value
+        .cpu_stats
+        .as_ref()
+        .and_then(|s| {
+            s.online_cpus.map(f64::from).or_else(|| {
+                s.cpu_usage
+                    .as_ref()
+                    .and_then(|u| u.percpu_usage.as_ref())
+                    .map(|p| p.len() as f64)
+            })
+        })
+        .unwrap_or(1.0);
+    cpu_delta as f64 / system_delta as f64 * online_cpus * 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bollard::models::{
+        ContainerCpuStats, ContainerCpuUsage, ContainerMemoryStats, ContainerNetworkStats,
+    };
+
+    use super::*;
+
+    fn cpu_stats(
+        total_usage: u64,
+        system_cpu_usage: u64,
+    ) -> ContainerCpuStats {
+        ContainerCpuStats {
+            cpu_usage: Some(ContainerCpuUsage {
+                total_usage: Some(total_usage),
+                ..Default::default()
+            }),
+            system_cpu_usage: Some(system_cpu_usage),
+            online_cpus: Some(4),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stats_test() {
+        let network = |rx, tx| {
+            ContainerNetworkStats {
+                rx_bytes: Some(rx),
+                tx_bytes: Some(tx),
+                ..Default::default()
+            }
+        };
+        let response = ContainerStatsResponse {
+            cpu_stats: Some(cpu_stats(300, 2_000)),
+            precpu_stats: Some(cpu_stats(100, 1_000)),
+            memory_stats: Some(ContainerMemoryStats {
+                usage: Some(1_000),
+                limit: Some(4_000),
+                stats: Some(HashMap::from([("inactive_file".to_string(), 200)])),
+                ..Default::default()
+            }),
+            networks: Some(HashMap::from([
+                ("eth0".to_string(), network(10, 20)),
+                ("eth1".to_string(), network(1, 2)),
+            ])),
+            ..Default::default()
+        };
+        assert_eq!(SCellContainerStats::from(response), SCellContainerStats {
+            cpu_percent: 80.0,
+            memory_usage: 800,
+            memory_limit: 4_000,
+            net_rx: 11,
+            net_tx: 22,
+            pids: None,
+        });
+    }
+
+    #[test]
+    fn stats_empty_test() {
+        assert_eq!(
+            SCellContainerStats::from(ContainerStatsResponse::default()),
+            SCellContainerStats::default()
+        );
+    }
+}
@@ -0,0 +1,16 @@
+//! `scell check` validates the blueprint without Docker, e.g. in the pre-commit hooks.
+
+use std::path::PathBuf;
+
+use crate::scell::{SCell, types::name::TargetName};
+
+pub fn check(
+    scell_path: PathBuf,
+    target: Option<TargetName>,
+    all_targets: bool,
+) -> color_eyre::Result<()> {
+    for target in SCell::check(scell_path, target, all_targets)? {
+        println!("✓ {target}");
+    }
+    Ok(())
+}
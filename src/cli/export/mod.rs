@@ -0,0 +1,289 @@
+//! `scell export` writes the 'Shell-Cell' as a standalone Docker Compose project, so it
+//! could be used without `scell`.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use bollard::models::ContainerCreateBody;
+
+use crate::{
+    buildkit::containers_config,
+    error::UserError,
+    scell::{SCell, types::name::TargetName},
+};
+
+const COMPOSE_FILE_NAME: &str = "compose.yaml";
+const SERVICES_DIR: &str = "services";
+
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct Compose {
+    name: String,
+    services: BTreeMap<String, ComposeService>,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct ComposeService {
+    build: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    depends_on: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ports: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    volumes: Vec<ComposeVolume>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    environment: Vec<String>,
+    labels: BTreeMap<String, String>,
+}
+
+/// Bind mount in the long syntax, so a missing host path fails the container start,
+/// instead of being created as an empty directory.
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct ComposeVolume {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    source: String,
+    target: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    read_only: bool,
+    bind: ComposeBind,
+}
+
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct ComposeBind {
+    create_host_path: bool,
+}
+
+impl ComposeVolume {
+    /// Parses the `host:container[:mode]` bind. The host path could contain `:`
+    /// (e.g. on Windows), the container path is always absolute.
+    fn from_bind(bind: &str) -> Option<Self> {
+        let (rest, last) = bind.rsplit_once(':')?;
+        let (source, target, mode) = if last.starts_with('/') {
+            (rest, last, None)
+        } else {
+            let (source, target) = rest.rsplit_once(':')?;
+            (source, target, Some(last))
+        };
+        Some(Self {
+            ty: "bind",
+            source: source.to_string(),
+            target: target.to_string(),
+            read_only: mode == Some("ro"),
+            bind: ComposeBind {
+                create_host_path: false,
+            },
+        })
+    }
+}
+
+impl ComposeService {
+    /// Describes the container, created with the `config` from the image, built from the
+    /// `build` context directory.
+    fn new(
+        build: String,
+        depends_on: Vec<String>,
+        config: ContainerCreateBody,
+    ) -> Self {
+        let host_config = config.host_config.unwrap_or_default();
+        let mut ports: Vec<String> = host_config
+            .port_bindings
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(|(container_port, bindings)| {
+                bindings.unwrap_or_default().into_iter().map(move |b| {
+                    // An empty host port lets Docker pick a random available host port
+                    let host_port = b.host_port.unwrap_or_default();
+                    match b.host_ip {
+                        Some(host_ip) => format!("{host_ip}:{host_port}:{container_port}"),
+                        None if host_port.is_empty() => container_port.clone(),
+                        None => format!("{host_port}:{container_port}"),
+                    }
+                })
+            })
+            .collect();
+        ports.sort();
+        Self {
+            build,
+            depends_on,
+            ports,
+            volumes: host_config
+                .binds
+                .unwrap_or_default()
+                .iter()
+                .filter_map(|b| ComposeVolume::from_bind(b))
+                .collect(),
+            environment: config.env.unwrap_or_default(),
+            labels: config.labels.unwrap_or_default().into_iter().collect(),
+        }
+    }
+}
+
+pub fn export(
+    scell_path: PathBuf,
+    target: Option<TargetName>,
+    dir: &Path,
+) -> color_eyre::Result<()> {
+    if dir.read_dir().is_ok_and(|mut d| d.next().is_some()) {
+        Err(UserError::wrap(format!(
+            "`{}` already exists and is not empty",
+            dir.display()
+        )))?;
+    }
+    let scell = SCell::compile(scell_path, target)?;
+    let compose = compose(&scell)?;
+
+    let main = scell.image().entry_point().to_string();
+    scell.image().unpack_context(&dir.join(&main))?;
+    for (name, service) in scell.services() {
+        service
+            .image
+            .unpack_context(&dir.join(SERVICES_DIR).join(name.to_string()))?;
+    }
+    let path = dir.join(COMPOSE_FILE_NAME);
+    std::fs::write(&path, yaml_serde::to_string(&compose)?)?;
+    println!("Created `{}`", path.display());
+    Ok(())
+}
+
+/// Returns the Docker Compose project, describing the main container and its services
+/// the same way, they are created by `scell`.
+fn compose(scell: &SCell) -> color_eyre::Result<Compose> {
+    let main = scell.image().entry_point().to_string();
+    if main == SERVICES_DIR {
+        Err(UserError::wrap(format!(
+            "Target '{main}' has the same name as the services directory, cannot export it"
+        )))?;
+    }
+    let service_names: Vec<String> = scell.services().map(|(name, _)| name.to_string()).collect();
+    if service_names.contains(&main) {
+        Err(UserError::wrap(format!(
+            "Service '{main}' has the same name as the entry point target, cannot export it"
+        )))?;
+    }
+
+    let services = containers_config(scell)?
+        .into_iter()
+        .map(|(service_name, config)| {
+            if let Some(name) = service_name {
+                let name = name.to_string();
+                let build = format!("./{SERVICES_DIR}/{name}");
+                (name, ComposeService::new(build, Vec::new(), config))
+            } else {
+                let build = format!("./{main}");
+                let service = ComposeService::new(build, service_names.clone(), config);
+                (main.clone(), service)
+            }
+        })
+        .collect();
+    Ok(Compose {
+        name: scell.container_id()?.to_string(),
+        services,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bollard::models::{HostConfig, PortBinding};
+    use test_case::test_case;
+
+    use super::*;
+
+    fn binding(
+        host_ip: Option<&str>,
+        host_port: Option<&str>,
+    ) -> PortBinding {
+        PortBinding {
+            host_ip: host_ip.map(ToString::to_string),
+            host_port: host_port.map(ToString::to_string),
+        }
+    }
+
+    #[test_case(binding(None, Some("8080")) => "8080:80/tcp" ; "host port")]
+    #[test_case(binding(None, None) => "80/tcp" ; "random host port")]
+    #[test_case(binding(Some("127.0.0.1"), Some("8080")) => "127.0.0.1:8080:80/tcp" ; "host ip")]
+    #[test_case(binding(Some("127.0.0.1"), None) => "127.0.0.1::80/tcp" ; "host ip, random host port")]
+    fn compose_port_test(binding: PortBinding) -> String {
+        let config = ContainerCreateBody {
+            host_config: Some(HostConfig {
+                port_bindings: Some(HashMap::from([("80/tcp".to_string(), Some(vec![binding]))])),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        ComposeService::new(String::new(), Vec::new(), config)
+            .ports
+            .concat()
+    }
+
+    #[test]
+    fn compose_test() {
+        let path = std::path::Path::new("src/scell/compile/tests/ok/services_config");
+        let scell = SCell::compile(path, None).unwrap();
+        let compose = compose(&scell).unwrap();
+
+        assert_eq!(compose.name, scell.container_id().unwrap().to_string());
+        let services: Vec<_> = compose
+            .services
+            .iter()
+            .map(|(name, s)| (name.as_str(), s.build.as_str(), s.depends_on.clone()))
+            .collect();
+        assert_eq!(services, vec![
+            ("cache", "./services/cache", vec![]),
+            ("db", "./services/db", vec![]),
+            ("main", "./main", vec![
+                "cache".to_string(),
+                "db".to_string()
+            ]),
+        ]);
+    }
+
+    #[test]
+    fn compose_forward_test() {
+        let path = std::path::Path::new("src/scell/compile/tests/ok/forward_config");
+        let scell = SCell::compile(path, None).unwrap();
+        let compose = compose(&scell).unwrap();
+
+        let (_, main) = compose
+            .services
+            .iter()
+            .find(|(name, _)| *name == "main")
+            .unwrap();
+        let volumes: Vec<_> = main
+            .volumes
+            .iter()
+            .map(|v| (v.source.as_str(), v.target.as_str(), v.read_only))
+            .collect();
+        assert_eq!(volumes, vec![
+            (
+                "${SSH_AUTH_SOCK:?the SSH agent is not running}",
+                "/run/scell/ssh-agent.sock",
+                false
+            ),
+            ("${HOME}/.gitconfig", "/etc/gitconfig", true),
+            ("${HOME}/.ssh/known_hosts", "/etc/ssh/ssh_known_hosts", true),
+        ]);
+        assert!(main.volumes.iter().all(|v| !v.bind.create_host_path));
+        assert_eq!(main.environment, vec![
+            "SSH_AUTH_SOCK=/run/scell/ssh-agent.sock"
+        ]);
+    }
+
+    #[test]
+    fn compose_services_target_test() {
+        let path = std::path::Path::new("src/scell/compile/tests/ok/services_target");
+        let scell = SCell::compile(path, Some("services".parse().unwrap())).unwrap();
+        assert!(compose(&scell).is_err());
+    }
+
+    #[test_case("/host:/container" => Some(("/host".into(), "/container".into(), false)) ; "read write")]
+    #[test_case("/host:/container:ro" => Some(("/host".into(), "/container".into(), true)) ; "read only")]
+    #[test_case("C:\\host:/container:ro" => Some(("C:\\host".into(), "/container".into(), true)) ; "windows host")]
+    #[test_case("/host" => None ; "no container path")]
+    fn compose_volume_test(bind: &str) -> Option<(String, String, bool)> {
+        ComposeVolume::from_bind(bind).map(|v| (v.source, v.target, v.read_only))
+    }
+}
@@ -0,0 +1,290 @@
+//! `scell graph` renders the dependency graph of the compiled blueprint.
+
+use std::{fmt::Write, path::PathBuf};
+
+use crate::scell::{
+    SCell,
+    graph::{GraphNode, SCellGraph},
+    types::name::TargetName,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT, e.g. `scell graph --format dot | dot -Tsvg > graph.svg`
+    Dot,
+    /// Mermaid flowchart, which could be embedded into the Markdown documents
+    Mermaid,
+    /// Plain text tree
+    Tree,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edge {
+    From,
+    Service,
+}
+
+pub fn graph(
+    scell_path: PathBuf,
+    target: Option<TargetName>,
+    format: GraphFormat,
+) -> color_eyre::Result<()> {
+    let graph = SCell::compile(scell_path, target)?.graph();
+    print!("{}", render(&graph, format));
+    Ok(())
+}
+
+fn render(
+    graph: &SCellGraph,
+    format: GraphFormat,
+) -> String {
+    match format {
+        GraphFormat::Dot => render_dot(graph),
+        GraphFormat::Mermaid => render_mermaid(graph),
+        GraphFormat::Tree => render_tree(graph),
+    }
+}
+
+/// Returns the node's label lines.
+fn label(node: &GraphNode) -> Vec<String> {
+    match node {
+        GraphNode::Target {
+            name,
+            location,
+            provides,
+        } => {
+            let mut lines = vec![name.to_string(), location.display().to_string()];
+            if !provides.is_empty() {
+                lines.push(format!("[{}]", provides.join(", ")));
+            }
+            lines
+        },
+        GraphNode::Image(image) => vec![format!("image: {image}")],
+        GraphNode::Dockerfile(path) => vec![format!("dockerfile: {}", path.display())],
+    }
+}
+
+/// Numbers all nodes of the graph, returning them with the edges between them.
+fn flatten(graph: &SCellGraph) -> (Vec<&GraphNode>, Vec<(usize, usize, Edge)>) {
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+    // The first node of each service chain, the nested services are linked to
+    let mut service_ids = Vec::new();
+    let chains = std::iter::once((&graph.chain, None))
+        .chain(graph.services.iter().map(|s| (&s.chain, Some(s))));
+    for (chain, service) in chains {
+        let first = nodes.len();
+        // Services of the main target are linked to it, which is always the first node
+        let mut parent = service.map(|s| {
+            let parent_id = s
+                .parent
+                .as_ref()
+                .and_then(|p| service_ids.iter().find(|(name, _)| name == &p))
+                .map_or(0, |(_, id)| *id);
+            (parent_id, Edge::Service)
+        });
+        for node in chain {
+            let id = nodes.len();
+            nodes.push(node);
+            if let Some((parent_id, edge)) = parent {
+                edges.push((parent_id, id, edge));
+            }
+            parent = Some((id, Edge::From));
+        }
+        if let Some(s) = service {
+            service_ids.push((&s.name, first));
+        }
+    }
+    (nodes, edges)
+}
+
+fn render_dot(graph: &SCellGraph) -> String {
+    let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+    let (nodes, edges) = flatten(graph);
+    let mut out = String::from("digraph scell {\n");
+    for (id, node) in nodes.iter().enumerate() {
+        let shape = if matches!(node, GraphNode::Target { .. }) {
+            "box"
+        } else {
+            "ellipse"
+        };
+        let label: Vec<_> = label(node).iter().map(|l| escape(l)).collect();
+        let _ = writeln!(
+            out,
+            "    n{id} [shape={shape}, label=\"{}\"];",
+            label.join("\\n")
+        );
+    }
+    for (from, to, edge) in edges {
+        let attrs = match edge {
+            Edge::From => "label=\"from\"",
+            Edge::Service => "label=\"service\", style=dashed",
+        };
+        let _ = writeln!(out, "    n{from} -> n{to} [{attrs}];");
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn render_mermaid(graph: &SCellGraph) -> String {
+    let escape = |s: &str| s.replace('"', "#quot;");
+    let (nodes, edges) = flatten(graph);
+    let mut out = String::from("flowchart TD\n");
+    for (id, node) in nodes.iter().enumerate() {
+        let label: Vec<_> = label(node).iter().map(|l| escape(l)).collect();
+        let label = label.join("<br/>");
+        let _ = if matches!(node, GraphNode::Target { .. }) {
+            writeln!(out, "    n{id}[\"{label}\"]")
+        } else {
+            writeln!(out, "    n{id}([\"{label}\"])")
+        };
+    }
+    for (from, to, edge) in edges {
+        let arrow = match edge {
+            Edge::From => "-->|from|",
+            Edge::Service => "-.->|service|",
+        };
+        let _ = writeln!(out, "    n{from} {arrow} n{to}");
+    }
+    out
+}
+
+fn render_tree(graph: &SCellGraph) -> String {
+    let (nodes, edges) = flatten(graph);
+    let mut out = String::new();
+    if let Some(root) = nodes.first() {
+        let _ = writeln!(out, "{}", label(root).join(" "));
+        render_tree_children(&mut out, &nodes, &edges, 0, "");
+    }
+    out
+}
+
+/// Renders the children of the node as the tree branches, with the edge kind.
+fn render_tree_children(
+    out: &mut String,
+    nodes: &[&GraphNode],
+    edges: &[(usize, usize, Edge)],
+    parent: usize,
+    indent: &str,
+) {
+    let children: Vec<_> = edges.iter().filter(|(from, ..)| *from == parent).collect();
+    for (i, (_, to, edge)) in children.iter().enumerate() {
+        let Some(node) = nodes.get(*to) else {
+            continue;
+        };
+        let (branch, next) = if i == children.len().saturating_sub(1) {
+            ("└─ ", "   ")
+        } else {
+            ("├─ ", "│  ")
+        };
+        let edge = match edge {
+            Edge::From => "from",
+            Edge::Service => "service",
+        };
+        let _ = writeln!(out, "{indent}{branch}{edge}: {}", label(node).join(" "));
+        render_tree_children(out, nodes, edges, *to, &format!("{indent}{next}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::scell::graph::ServiceGraph;
+
+    fn target(
+        name: &str,
+        location: &str,
+        provides: &[&'static str],
+    ) -> GraphNode {
+        GraphNode::Target {
+            name: name.parse().unwrap(),
+            location: location.into(),
+            provides: provides.to_vec(),
+        }
+    }
+
+    fn graph() -> SCellGraph {
+        SCellGraph {
+            chain: vec![
+                target("main", "/a", &["shell", "hang"]),
+                target("base", "/b", &["config"]),
+                GraphNode::Image("debian:13".to_string()),
+            ],
+            services: vec![
+                ServiceGraph {
+                    name: "db".parse().unwrap(),
+                    parent: None,
+                    chain: vec![
+                        target("db", "/a", &["hang"]),
+                        GraphNode::Dockerfile("/a/Dockerfile".into()),
+                    ],
+                },
+                ServiceGraph {
+                    name: "cache".parse().unwrap(),
+                    parent: Some("db".parse().unwrap()),
+                    chain: vec![
+                        target("cache", "/a", &["hang"]),
+                        GraphNode::Image("redis:7".to_string()),
+                    ],
+                },
+            ],
+        }
+    }
+
+    #[test_case(
+        GraphFormat::Tree,
+        "main /a [shell, hang]\n\
+         ├─ from: base /b [config]\n\
+         │  └─ from: image: debian:13\n\
+         └─ service: db /a [hang]\n\
+         \x20  ├─ from: dockerfile: /a/Dockerfile\n\
+         \x20  └─ service: cache /a [hang]\n\
+         \x20     └─ from: image: redis:7\n"
+        ; "tree"
+    )]
+    #[test_case(
+        GraphFormat::Dot,
+        "digraph scell {\n\
+         \x20   n0 [shape=box, label=\"main\\n/a\\n[shell, hang]\"];\n\
+         \x20   n1 [shape=box, label=\"base\\n/b\\n[config]\"];\n\
+         \x20   n2 [shape=ellipse, label=\"image: debian:13\"];\n\
+         \x20   n3 [shape=box, label=\"db\\n/a\\n[hang]\"];\n\
+         \x20   n4 [shape=ellipse, label=\"dockerfile: /a/Dockerfile\"];\n\
+         \x20   n5 [shape=box, label=\"cache\\n/a\\n[hang]\"];\n\
+         \x20   n6 [shape=ellipse, label=\"image: redis:7\"];\n\
+         \x20   n0 -> n1 [label=\"from\"];\n\
+         \x20   n1 -> n2 [label=\"from\"];\n\
+         \x20   n0 -> n3 [label=\"service\", style=dashed];\n\
+         \x20   n3 -> n4 [label=\"from\"];\n\
+         \x20   n3 -> n5 [label=\"service\", style=dashed];\n\
+         \x20   n5 -> n6 [label=\"from\"];\n\
+         }\n"
+        ; "dot"
+    )]
+    #[test_case(
+        GraphFormat::Mermaid,
+        "flowchart TD\n\
+         \x20   n0[\"main<br/>/a<br/>[shell, hang]\"]\n\
+         \x20   n1[\"base<br/>/b<br/>[config]\"]\n\
+         \x20   n2([\"image: debian:13\"])\n\
+         \x20   n3[\"db<br/>/a<br/>[hang]\"]\n\
+         \x20   n4([\"dockerfile: /a/Dockerfile\"])\n\
+         \x20   n5[\"cache<br/>/a<br/>[hang]\"]\n\
+         \x20   n6([\"image: redis:7\"])\n\
+         \x20   n0 -->|from| n1\n\
+         \x20   n1 -->|from| n2\n\
+         \x20   n0 -.->|service| n3\n\
+         \x20   n3 -->|from| n4\n\
+         \x20   n3 -.->|service| n5\n\
+         \x20   n5 -->|from| n6\n"
+        ; "mermaid"
+    )]
+    fn render_test(
+        format: GraphFormat,
+        expected: &str,
+    ) {
+        assert_eq!(render(&graph(), format), expected);
+    }
+}
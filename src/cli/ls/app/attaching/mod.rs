@@ -0,0 +1,81 @@
+mod ui;
+
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+
+use crate::{
+    buildkit::container_info::SCellContainerInfo,
+    cli::{
+        MIN_FPS,
+        ls::app::{AppInner, AppItemSuperTrait, error_window::ErrorWindowState, ls::LsState},
+    },
+    pty::Pty,
+};
+
+/// Holds the state while the item is being started (if needed) and attached to in the
+/// background.
+pub struct AttachingState<Item> {
+    pub for_attach: Item,
+    pub ls_state: LsState<Item>,
+    pub rx: Receiver<color_eyre::Result<Pty>>,
+}
+
+/// The item's shell session, ready to be run.
+pub struct AttachedState<Item> {
+    pub attached: Item,
+    pub pty: Pty,
+    /// The list state to refresh when the session is finished.
+    pub ls_state: LsState<Item>,
+}
+
+impl AttachingState<SCellContainerInfo> {
+    /// Spawns a background task that starts `container`, if it is not running, and
+    /// attaches to its shell, returning a [`AttachingState`] to track progress.
+    pub fn attach(
+        ls_state: LsState<SCellContainerInfo>,
+        for_attach: SCellContainerInfo,
+    ) -> AppInner<SCellContainerInfo> {
+        let buildkit = ls_state.buildkit.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+        tokio::spawn({
+            let container = for_attach.clone();
+            async move {
+                drop(tx.send(buildkit.attach_to_container(&container).await));
+            }
+        });
+        AppInner::Attaching(Self {
+            for_attach,
+            ls_state,
+            rx,
+        })
+    }
+}
+
+impl<Item: Clone + AppItemSuperTrait> AttachingState<Item> {
+    /// Polls the background attach task for completion and returns the next app state.
+    ///
+    /// - [`AppInner::Attaching`] — still waiting (self is returned back)
+    /// - [`AppInner::Attached`] — attach succeeded; contains the shell session
+    pub fn try_recv(self) -> color_eyre::Result<AppInner<Item>> {
+        match self.rx.recv_timeout(MIN_FPS) {
+            Ok(Ok(pty)) => {
+                Ok(AppInner::Attached(Box::new(AttachedState {
+                    attached: self.for_attach,
+                    pty,
+                    ls_state: self.ls_state,
+                })))
+            },
+            Ok(Err(e)) => {
+                Ok(AppInner::ErrorWindow(ErrorWindowState {
+                    ls_state: self.ls_state,
+                    message: e.to_string(),
+                }))
+            },
+            Err(RecvTimeoutError::Timeout) => Ok(AppInner::Attaching(self)),
+            Err(RecvTimeoutError::Disconnected) => {
+                color_eyre::eyre::bail!(
+                    "AttachingState channel cannot be disconnected without returning a result"
+                )
+            },
+        }
+    }
+}
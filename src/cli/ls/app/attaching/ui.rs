@@ -0,0 +1,66 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::{
+    buildkit::container_info::SCellContainerInfo, cli::ls::app::attaching::AttachingState,
+};
+
+impl Widget for &AttachingState<SCellContainerInfo> {
+    #[allow(clippy::indexing_slicing)]
+    fn render(
+        self,
+        area: Rect,
+        buf: &mut ratatui::prelude::Buffer,
+    ) where
+        Self: Sized,
+    {
+        self.ls_state.render(area, buf);
+
+        let vertical = Layout::vertical([
+            Constraint::Percentage(40),
+            Constraint::Percentage(20),
+            Constraint::Percentage(40),
+        ])
+        .split(area);
+
+        let horizontal = Layout::horizontal([
+            Constraint::Percentage(20),
+            Constraint::Percentage(60),
+            Constraint::Percentage(20),
+        ])
+        .split(vertical[1]);
+
+        let attaching_text = vec![
+            Line::from(vec![
+                Span::styled(
+                    "Attaching",
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled("...", Style::default().fg(Color::Green)),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("Attaching to '{}'", self.for_attach.id),
+                Style::default().fg(Color::Gray),
+            )),
+        ];
+
+        Widget::render(Clear, horizontal[1], buf);
+
+        let paragraph = Paragraph::new(attaching_text)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Green)),
+            )
+            .centered();
+
+        paragraph.render(horizontal[1], buf);
+    }
+}
@@ -5,12 +5,13 @@ use crate::{
     cli::ls::app::{AppInner, AppItemSuperTrait, ls::LsState, removing::RemovingState},
 };
 
-/// Holds the state while waiting for user confirmation to remove an item.
+/// Holds the state while waiting for user confirmation to remove the items.
 ///
 /// Displays a warning that all item's state will be lost and waits for
 /// the user to press 'y' (confirm) or 'n'/'Esc' (cancel).
 pub struct ConfirmRemoveState<Item> {
-    pub selected_to_remove: Item,
+    /// The shown marked items or the selected one, never empty.
+    pub selected_to_remove: Vec<Item>,
     pub ls_state: LsState<Item>,
 }
 
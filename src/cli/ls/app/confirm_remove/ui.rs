@@ -2,12 +2,12 @@ use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph, Widget},
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
 };
 
 use crate::{
     buildkit::{container_info::SCellContainerInfo, image_info::SCellImageInfo},
-    cli::ls::app::confirm_remove::ConfirmRemoveState,
+    cli::ls::app::{confirm_remove::ConfirmRemoveState, ls::LsItem},
 };
 
 impl Widget for &ConfirmRemoveState<SCellContainerInfo> {
@@ -42,11 +42,15 @@ impl Widget for &ConfirmRemoveState<SCellContainerInfo> {
             )]),
             Line::from(""),
             Line::from(Span::styled(
-                format!("Remove container '{}'?", self.selected_to_remove.id),
+                question("container", &self.selected_to_remove),
                 Style::default()
                     .fg(Color::White)
                     .add_modifier(Modifier::BOLD),
             )),
+            Line::from(Span::styled(
+                hidden_note(self.ls_state.hidden_marked()),
+                Style::default().fg(Color::Gray),
+            )),
             Line::from(""),
             Line::from(Span::styled(
                 "This will permanently delete:",
@@ -91,7 +95,8 @@ impl Widget for &ConfirmRemoveState<SCellContainerInfo> {
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(Color::Red)),
             )
-            .centered();
+            .centered()
+            .wrap(Wrap { trim: true });
 
         paragraph.render(horizontal[1], buf);
     }
@@ -129,11 +134,15 @@ impl Widget for &ConfirmRemoveState<SCellImageInfo> {
             )]),
             Line::from(""),
             Line::from(Span::styled(
-                format!("Remove image '{}'?", self.selected_to_remove.id),
+                question("image", &self.selected_to_remove),
                 Style::default()
                     .fg(Color::White)
                     .add_modifier(Modifier::BOLD),
             )),
+            Line::from(Span::styled(
+                hidden_note(self.ls_state.hidden_marked()),
+                Style::default().fg(Color::Gray),
+            )),
             Line::from(""),
             Line::from(Span::styled(
                 "This will permanently delete:",
@@ -174,8 +183,35 @@ impl Widget for &ConfirmRemoveState<SCellImageInfo> {
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(Color::Red)),
             )
-            .centered();
+            .centered()
+            .wrap(Wrap { trim: true });
 
         paragraph.render(horizontal[1], buf);
     }
 }
+
+/// Returns the removal question, listing all items to remove.
+fn question(
+    kind: &str,
+    items: &[impl LsItem],
+) -> String {
+    match items {
+        [item] => format!("Remove {kind} '{}'?", item.key()),
+        items => {
+            let listed: Vec<_> = items
+                .iter()
+                .map(|item| format!("'{}'", item.key()))
+                .collect();
+            format!("Remove {} {kind}s ({})?", items.len(), listed.join(", "))
+        },
+    }
+}
+
+/// Returns the note about the marked items, hidden by the filter and kept, if any.
+fn hidden_note(hidden: usize) -> String {
+    match hidden {
+        0 => String::new(),
+        1 => "1 marked item, hidden by the filter, is kept".to_string(),
+        n => format!("{n} marked items, hidden by the filter, are kept"),
+    }
+}
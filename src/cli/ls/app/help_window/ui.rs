@@ -20,6 +20,28 @@ const CONTAINER_HELP_ENTRIES: &[HelpEntry] = &[
         key_color: Color::Yellow,
         description: "Move selection",
     },
+    HelpEntry::Shortcut {
+        key: "/",
+        key_color: Color::Yellow,
+        description: "Filter by ID, target or location",
+    },
+    HelpEntry::Note("(Enter: keep, Esc: clear)"),
+    HelpEntry::Shortcut {
+        key: "o / O",
+        key_color: Color::Yellow,
+        description: "Sort by created at, status, location / reverse",
+    },
+    HelpEntry::Shortcut {
+        key: "Space",
+        key_color: Color::Yellow,
+        description: "Mark / unmark selected container",
+    },
+    HelpEntry::Shortcut {
+        key: "A",
+        key_color: Color::Yellow,
+        description: "Mark all shown orphaned containers",
+    },
+    HelpEntry::Note("(Esc: clear the marks)"),
     HelpEntry::Blank,
     HelpEntry::Section("Actions"),
     HelpEntry::Shortcut {
@@ -27,20 +49,27 @@ const CONTAINER_HELP_ENTRIES: &[HelpEntry] = &[
         key_color: Color::Yellow,
         description: "Switch to the images view",
     },
+    HelpEntry::Shortcut {
+        key: "Enter / a",
+        key_color: Color::Yellow,
+        description: "Attach to selected container",
+    },
+    HelpEntry::Note("(starts it, if stopped)"),
     HelpEntry::Shortcut {
         key: "i",
         key_color: Color::Yellow,
         description: "Inspect container definition",
     },
+    HelpEntry::Note("(Tab: switch to the logs and stats)"),
     HelpEntry::Shortcut {
         key: "s",
         key_color: Color::Yellow,
-        description: "Stop selected container",
+        description: "Stop marked or selected containers",
     },
     HelpEntry::Shortcut {
         key: "r",
         key_color: Color::Yellow,
-        description: "Remove selected container",
+        description: "Remove marked or selected containers",
     },
     HelpEntry::Blank,
     HelpEntry::Section("General"),
@@ -60,6 +89,28 @@ const IMAGE_HELP_ENTRIES: &[HelpEntry] = &[
         key_color: Color::Yellow,
         description: "Move selection",
     },
+    HelpEntry::Shortcut {
+        key: "/",
+        key_color: Color::Yellow,
+        description: "Filter by ID, target or location",
+    },
+    HelpEntry::Note("(Enter: keep, Esc: clear)"),
+    HelpEntry::Shortcut {
+        key: "o / O",
+        key_color: Color::Yellow,
+        description: "Sort by created at, status, location / reverse",
+    },
+    HelpEntry::Shortcut {
+        key: "Space",
+        key_color: Color::Yellow,
+        description: "Mark / unmark selected image",
+    },
+    HelpEntry::Shortcut {
+        key: "A",
+        key_color: Color::Yellow,
+        description: "Mark all shown orphaned images",
+    },
+    HelpEntry::Note("(Esc: clear the marks)"),
     HelpEntry::Blank,
     HelpEntry::Section("Actions"),
     HelpEntry::Shortcut {
@@ -75,7 +126,7 @@ const IMAGE_HELP_ENTRIES: &[HelpEntry] = &[
     HelpEntry::Shortcut {
         key: "r",
         key_color: Color::Yellow,
-        description: "Remove selected image",
+        description: "Remove marked or selected images",
     },
     HelpEntry::Note("(can't remove image, which is in use)"),
     HelpEntry::Blank,
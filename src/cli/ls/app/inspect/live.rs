@@ -0,0 +1,186 @@
+//! Live logs and resource stats of the inspected container.
+
+use std::sync::mpsc::{Receiver, Sender};
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+
+use crate::{
+    buildkit::{BuildKitD, container_info::SCellContainerInfo, stats_info::SCellContainerStats},
+    pty::{AnsiStripper, Utf8Decoder},
+};
+
+/// Number of the last log lines, which are fetched and kept.
+const LOGS_TAIL: usize = 500;
+/// Tab stops interval, the tabs in the logs are expanded with.
+const TAB_WIDTH: usize = 8;
+
+enum LiveUpdate {
+    Logs(Bytes),
+    Stats(SCellContainerStats),
+    Error(String),
+}
+
+/// Follows the logs and the resource stats of the container in the background, until it
+/// is dropped.
+pub struct LiveData {
+    /// The last log lines of the container's main process.
+    pub logs: Vec<String>,
+    /// The last log line is not finished yet.
+    partial: bool,
+    /// Removes escape sequences and control characters from the logs, so they do not
+    /// break the rendering.
+    stripper: AnsiStripper,
+    /// Decodes the logs, carrying a multibyte character split between the chunks over.
+    decoder: Utf8Decoder,
+    /// The last resource usage sample, `None` until the first one is received.
+    pub stats: Option<SCellContainerStats>,
+    /// The error, the logs or the stats stream failed with.
+    pub error: Option<String>,
+    rx: Receiver<LiveUpdate>,
+    handles: Vec<tokio::task::JoinHandle<()>>,
+}
+
+impl LiveData {
+    /// Spawns async tasks that follow the logs and the resource stats of the container.
+    pub fn spawn(
+        buildkit: &BuildKitD,
+        container: &SCellContainerInfo,
+    ) -> Self {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let handles = vec![
+            tokio::spawn(forward(
+                buildkit.container_logs(container, LOGS_TAIL),
+                tx.clone(),
+                LiveUpdate::Logs,
+            )),
+            tokio::spawn(forward(
+                buildkit.container_stats(container),
+                tx,
+                LiveUpdate::Stats,
+            )),
+        ];
+        Self {
+            logs: Vec::new(),
+            partial: false,
+            stripper: AnsiStripper::default(),
+            decoder: Utf8Decoder::default(),
+            stats: None,
+            error: None,
+            rx,
+            handles,
+        }
+    }
+
+    /// Applies the pending updates, without blocking.
+    pub fn update(&mut self) {
+        while let Ok(update) = self.rx.try_recv() {
+            match update {
+                LiveUpdate::Logs(output) => self.push_logs(&output),
+                LiveUpdate::Stats(stats) => self.stats = Some(stats),
+                LiveUpdate::Error(e) => self.error = Some(e),
+            }
+        }
+    }
+
+    /// Appends the output to the logs, continuing the unfinished last line and keeping
+    /// only the last [`LOGS_TAIL`] lines.
+    /// Escape sequences and control characters are removed, tabs are expanded.
+    fn push_logs(
+        &mut self,
+        output: &[u8],
+    ) {
+        let stripped = self.stripper.strip(output);
+        let output = self.decoder.decode(&stripped);
+        for (i, line) in output.split('\n').enumerate() {
+            match self.logs.last_mut() {
+                Some(last) if i == 0 && self.partial => push_expanded(last, line),
+                _ => {
+                    let mut new = String::new();
+                    push_expanded(&mut new, line);
+                    self.logs.push(new);
+                },
+            }
+        }
+        // The output, ending with the new line, leaves an empty unfinished line
+        self.partial = !output.ends_with('\n');
+        if !self.partial {
+            self.logs.pop();
+        }
+        let excess = self.logs.len().saturating_sub(LOGS_TAIL);
+        self.logs.drain(..excess);
+    }
+}
+
+impl Drop for LiveData {
+    fn drop(&mut self) {
+        self.handles.iter().for_each(tokio::task::JoinHandle::abort);
+    }
+}
+
+/// Appends the text to the line, expanding the tabs to the next [`TAB_WIDTH`] stop.
+fn push_expanded(
+    line: &mut String,
+    text: &str,
+) {
+    for c in text.chars() {
+        if c == '\t' {
+            let width = TAB_WIDTH.saturating_sub(line.chars().count() % TAB_WIDTH);
+            line.extend(std::iter::repeat_n(' ', width));
+        } else {
+            line.push(c);
+        }
+    }
+}
+
+/// Forwards the stream items as the updates, until the stream ends or the receiver is
+/// dropped.
+async fn forward<T>(
+    stream: impl Stream<Item = color_eyre::Result<T>>,
+    tx: Sender<LiveUpdate>,
+    to_update: impl Fn(T) -> LiveUpdate,
+) {
+    let mut stream = std::pin::pin!(stream);
+    while let Some(item) = stream.next().await {
+        let update = item.map_or_else(|e| LiveUpdate::Error(e.to_string()), &to_update);
+        if tx.send(update).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(&[b"a\n", b"b\n"], &["a", "b"] ; "full lines")]
+    #[test_case(&[b"a\nb", b"c\n"], &["a", "bc"] ; "partial line")]
+    #[test_case(&[b"a", b"b", b"\nc"], &["ab", "c"] ; "partial lines")]
+    #[test_case(&[b"a\n\n"], &["a", ""] ; "empty line")]
+    #[test_case(&[b"\x1b[31mred\x1b[0m\r\n"], &["red"] ; "escape sequences")]
+    #[test_case(&[b"a\x1b[", b"1mb\n"], &["ab"] ; "split escape sequence")]
+    #[test_case(&[b"a\tb", b"\tc\n"], &["a       b       c"] ; "tabs")]
+    #[test_case(&[b"a\xE4\xB8", b"\xADb\n"], &["a中b"] ; "split character")]
+    fn push_logs_test(
+        outputs: &[&[u8]],
+        expected: &[&str],
+    ) {
+        let (_tx, rx) = std::sync::mpsc::channel();
+        let mut live = LiveData {
+            logs: Vec::new(),
+            partial: false,
+            stripper: AnsiStripper::default(),
+            decoder: Utf8Decoder::default(),
+            stats: None,
+            error: None,
+            rx,
+            handles: Vec::new(),
+        };
+        for output in outputs {
+            live.push_logs(output);
+        }
+        assert_eq!(live.logs, expected);
+    }
+}
@@ -1,10 +1,11 @@
+mod live;
 mod ui;
 
 use tui_scrollview::ScrollViewState;
 
 use crate::{
     buildkit::{container_info::SCellContainerInfo, image_info::SCellImageInfo},
-    cli::ls::app::{AppInner, AppItemSuperTrait, ls::LsState},
+    cli::ls::app::{AppInner, AppItemSuperTrait, inspect::live::LiveData, ls::LsState},
 };
 
 pub trait ItemToInspect {
@@ -13,12 +14,37 @@ pub trait ItemToInspect {
     fn inspect_data(&self) -> color_eyre::Result<Self::Data>;
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InspectTab {
+    Definition,
+    Logs,
+    Stats,
+}
+
+impl InspectTab {
+    pub const ALL: [Self; 3] = [Self::Definition, Self::Logs, Self::Stats];
+
+    fn next(self) -> Self {
+        match self {
+            Self::Definition => Self::Logs,
+            Self::Logs => Self::Stats,
+            Self::Stats => Self::Definition,
+        }
+    }
+}
+
 /// Holds the state when the user is viewing the inspect overlay.
 pub struct InspectState<Item: ItemToInspect> {
     /// The list state to restore when the overlay is dismissed.
     pub ls_state: LsState<Item>,
     data: Item::Data,
     scroll_state: ScrollViewState,
+    tab: InspectTab,
+    /// Live logs and stats, available only for the containers.
+    live: Option<LiveData>,
+    logs_scroll_state: ScrollViewState,
+    /// The logs view is scrolled to the bottom, following the new output.
+    follow_logs: bool,
 }
 
 impl<Item: ItemToInspect + AppItemSuperTrait> InspectState<Item> {
@@ -26,30 +52,79 @@ impl<Item: ItemToInspect + AppItemSuperTrait> InspectState<Item> {
         ls_state: LsState<Item>,
         item: &Item,
     ) -> color_eyre::Result<AppInner<Item>> {
-        Ok(AppInner::Inspect(Self {
+        Ok(AppInner::Inspect(Self::new(ls_state, item, None)?))
+    }
+
+    fn new(
+        ls_state: LsState<Item>,
+        item: &Item,
+        live: Option<LiveData>,
+    ) -> color_eyre::Result<Self> {
+        Ok(Self {
             ls_state,
             data: item.inspect_data()?,
             scroll_state: ScrollViewState::new(),
-        }))
+            tab: InspectTab::Definition,
+            live,
+            logs_scroll_state: ScrollViewState::new(),
+            follow_logs: true,
+        })
+    }
+
+    /// Applies the pending live updates, if any.
+    pub fn update(&mut self) {
+        if let Some(live) = &mut self.live {
+            live.update();
+        }
+    }
+
+    /// Switches to the next tab, if the live data is available.
+    pub fn next_tab(&mut self) {
+        if self.live.is_some() {
+            self.tab = self.tab.next();
+        }
     }
 
     /// Scroll up
     pub fn scroll_up(&mut self) {
-        self.scroll_state.scroll_up();
+        if self.tab == InspectTab::Logs {
+            self.follow_logs = false;
+            self.logs_scroll_state.scroll_up();
+        } else {
+            self.scroll_state.scroll_up();
+        }
     }
 
-    /// Scroll down
+    /// Scroll down, following the new logs again, once the bottom is reached.
     pub fn scroll_down(&mut self) {
-        self.scroll_state.scroll_down();
+        if self.tab == InspectTab::Logs {
+            self.logs_scroll_state.scroll_down();
+            self.follow_logs = self.logs_scroll_state.is_at_bottom();
+        } else {
+            self.scroll_state.scroll_down();
+        }
+    }
+}
+
+impl InspectState<SCellContainerInfo> {
+    /// Same as [`InspectState::inspect`], but also follows the container's logs and
+    /// resource stats.
+    pub fn inspect_live(
+        ls_state: LsState<SCellContainerInfo>,
+        item: &SCellContainerInfo,
+    ) -> color_eyre::Result<AppInner<SCellContainerInfo>> {
+        let live = LiveData::spawn(&ls_state.buildkit, item);
+        Ok(AppInner::Inspect(Self::new(ls_state, item, Some(live))?))
     }
 }
 
 type ContainerDescription = Option<String>;
 type ImageDescription = Option<String>;
 type ImageId = Option<String>;
+type PublishedPorts = Vec<String>;
 
 impl ItemToInspect for SCellContainerInfo {
-    type Data = (ContainerDescription, ImageId);
+    type Data = (ContainerDescription, ImageId, PublishedPorts);
 
     fn inspect_data(&self) -> color_eyre::Result<Self::Data> {
         let description = self
@@ -59,7 +134,8 @@ impl ItemToInspect for SCellContainerInfo {
             .transpose()?
             .clone();
         let image_id = self.image_id.as_ref().map(ToString::to_string);
-        Ok((description, image_id))
+        let ports = self.ports.iter().map(ToString::to_string).collect();
+        Ok((description, image_id, ports))
     }
 }
 
@@ -1,18 +1,23 @@
+use indicatif::HumanBytes;
 use ratatui::{
     layout::{Constraint, HorizontalAlignment, Layout, Rect, Size},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Tabs, Widget},
 };
 use tui_scrollview::{ScrollView, ScrollViewState, ScrollbarVisibility};
 
 use crate::{
     buildkit::{container_info::SCellContainerInfo, image_info::SCellImageInfo},
-    cli::ls::app::inspect::InspectState,
+    cli::ls::app::inspect::{InspectState, InspectTab, live::LiveData},
 };
 
+/// Hint rendered on the bottom border of the container's overlay.
+const CONTAINER_FOOTER: &str = "Tab: switch tab, i / Esc: close this window";
+/// Hint rendered on the bottom border of the image's overlay.
+const IMAGE_FOOTER: &str = "i / Esc: close this window";
+
 impl Widget for &mut InspectState<SCellContainerInfo> {
-    #[allow(clippy::indexing_slicing)]
     fn render(
         self,
         area: ratatui::prelude::Rect,
@@ -21,28 +26,71 @@ impl Widget for &mut InspectState<SCellContainerInfo> {
         Self: Sized,
     {
         self.ls_state.render(area, buf);
-        let (container_desc, image_id) = &self.data;
 
-        let window_area = prepare_window_area(area, buf);
+        let window_area = prepare_window_area(" Inspect ", CONTAINER_FOOTER, area, buf);
+        let [tabs_area, content_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).areas(window_area);
+        render_tabs(self.tab, tabs_area, buf);
+
+        match self.tab {
+            InspectTab::Definition => self.render_definition(content_area, buf),
+            InspectTab::Logs => {
+                render_logs(
+                    self.live.as_ref(),
+                    self.follow_logs,
+                    &mut self.logs_scroll_state,
+                    content_area,
+                    buf,
+                );
+            },
+            InspectTab::Stats => render_stats(self.live.as_ref(), content_area, buf),
+        }
+    }
+}
+
+impl InspectState<SCellContainerInfo> {
+    #[allow(clippy::indexing_slicing)]
+    fn render_definition(
+        &mut self,
+        window_area: Rect,
+        buf: &mut ratatui::prelude::Buffer,
+    ) {
+        let (container_desc, image_id, ports) = &self.data;
 
         let vertical = Layout::vertical([
-            Constraint::Length(2),       // image_id line + horizontal separator
+            Constraint::Length(3),       // image_id and ports lines + horizontal separator
             Constraint::Percentage(100), // description scroll area
         ])
         .split(window_area);
 
-        Paragraph::new(Line::from(vec![
-            Span::styled(
-                "Image ID: ",
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                image_id.as_deref().unwrap_or("N/A"),
-                Style::default().fg(Color::White),
-            ),
-        ]))
+        let ports = if ports.is_empty() {
+            "N/A".to_string()
+        } else {
+            ports.join(", ")
+        };
+        Paragraph::new(vec![
+            Line::from(vec![
+                Span::styled(
+                    "Image ID: ",
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    image_id.as_deref().unwrap_or("N/A"),
+                    Style::default().fg(Color::White),
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    "Ports: ",
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(ports, Style::default().fg(Color::White)),
+            ]),
+        ])
         .alignment(HorizontalAlignment::Center)
         .render(vertical[0], buf);
 
@@ -66,7 +114,7 @@ impl Widget for &mut InspectState<SCellImageInfo> {
         self.ls_state.render(area, buf);
         let image_desc = &self.data;
 
-        let window_area = prepare_window_area(area, buf);
+        let window_area = prepare_window_area(" Definition ", IMAGE_FOOTER, area, buf);
         render_description(
             image_desc.as_deref(),
             &mut self.scroll_state,
@@ -78,6 +126,8 @@ impl Widget for &mut InspectState<SCellImageInfo> {
 
 #[allow(clippy::indexing_slicing)]
 fn prepare_window_area(
+    title: &str,
+    footer: &str,
     area: ratatui::prelude::Rect,
     buf: &mut ratatui::prelude::Buffer,
 ) -> ratatui::prelude::Rect {
@@ -100,8 +150,8 @@ fn prepare_window_area(
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .title(" Definition ")
-        .title_bottom("i / Esc: close this window")
+        .title(title)
+        .title_bottom(footer)
         .title_alignment(HorizontalAlignment::Center)
         .border_style(Style::default().fg(Color::Cyan));
     let inner_window_area = block.inner(window_area);
@@ -136,3 +186,136 @@ fn render_description(
     scroll_view.render_widget(paragraph, Rect::new(0, 0, area.width, content_height));
     scroll_view.render(area, buf, state);
 }
+
+fn render_tabs(
+    tab: InspectTab,
+    area: Rect,
+    buf: &mut ratatui::prelude::Buffer,
+) {
+    let titles = InspectTab::ALL.map(|tab| {
+        match tab {
+            InspectTab::Definition => "Definition",
+            InspectTab::Logs => "Logs",
+            InspectTab::Stats => "Stats",
+        }
+    });
+    Tabs::new(titles)
+        .select(InspectTab::ALL.iter().position(|t| *t == tab))
+        .style(Style::default().fg(Color::Gray))
+        .highlight_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        )
+        .render(area, buf);
+}
+
+/// Renders the container's logs, scrolled to the bottom while they are followed.
+fn render_logs(
+    live: Option<&LiveData>,
+    follow: bool,
+    state: &mut ScrollViewState,
+    area: Rect,
+    buf: &mut ratatui::prelude::Buffer,
+) {
+    let Some(live) = live else {
+        return;
+    };
+    let mut lines: Vec<Line> = live
+        .logs
+        .iter()
+        .map(|l| Line::from(Span::styled(l.as_str(), Style::default().fg(Color::White))))
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No logs available",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    if let Some(e) = &live.error {
+        lines.push(Line::from(Span::styled(
+            e.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    let content_height = u16::try_from(lines.len()).unwrap_or(u16::MAX);
+    let mut scroll_view = ScrollView::new(Size::new(area.width, content_height))
+        .vertical_scrollbar_visibility(ScrollbarVisibility::Automatic)
+        .horizontal_scrollbar_visibility(ScrollbarVisibility::Never);
+    scroll_view.render_widget(
+        Paragraph::new(lines),
+        Rect::new(0, 0, area.width, content_height),
+    );
+    if follow {
+        state.scroll_to_bottom();
+    }
+    scroll_view.render(area, buf, state);
+}
+
+/// Renders the last resource usage sample of the container.
+fn render_stats(
+    live: Option<&LiveData>,
+    area: Rect,
+    buf: &mut ratatui::prelude::Buffer,
+) {
+    let Some(live) = live else {
+        return;
+    };
+    let entry = |name: &'static str, value: String| {
+        Line::from(vec![
+            Span::styled(
+                name,
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(value, Style::default().fg(Color::White)),
+        ])
+    };
+    let mut lines = match &live.stats {
+        Some(stats) => {
+            vec![
+                entry("CPU: ", format!("{:.2}%", stats.cpu_percent)),
+                entry(
+                    "Memory: ",
+                    format!(
+                        "{} / {}",
+                        HumanBytes(stats.memory_usage),
+                        HumanBytes(stats.memory_limit)
+                    ),
+                ),
+                entry(
+                    "Network (rx / tx): ",
+                    format!(
+                        "{} / {}",
+                        HumanBytes(stats.net_rx),
+                        HumanBytes(stats.net_tx)
+                    ),
+                ),
+                entry(
+                    "PIDs: ",
+                    stats
+                        .pids
+                        .map_or_else(|| "N/A".to_string(), |p| p.to_string()),
+                ),
+            ]
+        },
+        None => {
+            vec![Line::from(Span::styled(
+                "Waiting for the stats...",
+                Style::default().fg(Color::DarkGray),
+            ))]
+        },
+    };
+    if let Some(e) = &live.error {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            e.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    Paragraph::new(lines)
+        .alignment(HorizontalAlignment::Center)
+        .render(area, buf);
+}
@@ -5,7 +5,10 @@ use crate::{
     buildkit::{BuildKitD, container_info::SCellContainerInfo, image_info::SCellImageInfo},
     cli::{
         MIN_FPS,
-        ls::app::{AppInner, AppItemSuperTrait, ls::LsState},
+        ls::app::{
+            AppInner, AppItemSuperTrait,
+            ls::{LsState, Watcher},
+        },
     },
 };
 
@@ -13,12 +16,33 @@ use crate::{
 pub struct LoadingState<Item> {
     buildkit: BuildKitD,
     rx: Receiver<color_eyre::Result<Vec<Item>>>,
+    /// The list state to refresh with the loaded items, preserving its view and
+    /// selection.
+    prev: Option<LsState<Item>>,
+    /// Live updates of the items, started before the list is fetched, so no changes
+    /// are missed.
+    watcher: Option<Watcher<Item>>,
 }
 
 impl LoadingState<SCellContainerInfo> {
     /// Creates a new [`LoadingState<SCellContainerInfo>`], spawning an async task
     /// that fetches the current Shell-Cell image list.
     pub fn load(buildkit: BuildKitD) -> AppInner<SCellContainerInfo> {
+        let watcher = Watcher::containers(buildkit.clone());
+        Self::spawn(buildkit, None, Some(watcher))
+    }
+
+    /// Same as [`LoadingState::load`], but refreshes the `ls_state` once the list is
+    /// fetched.
+    pub fn reload(ls_state: LsState<SCellContainerInfo>) -> AppInner<SCellContainerInfo> {
+        Self::spawn(ls_state.buildkit.clone(), Some(ls_state), None)
+    }
+
+    fn spawn(
+        buildkit: BuildKitD,
+        prev: Option<LsState<SCellContainerInfo>>,
+        watcher: Option<Watcher<SCellContainerInfo>>,
+    ) -> AppInner<SCellContainerInfo> {
         let (tx, rx) = std::sync::mpsc::channel();
         tokio::spawn({
             let buildkit = buildkit.clone();
@@ -33,7 +57,12 @@ impl LoadingState<SCellContainerInfo> {
             }
         });
 
-        AppInner::Loading(Self { buildkit, rx })
+        AppInner::Loading(Self {
+            buildkit,
+            rx,
+            prev,
+            watcher,
+        })
     }
 }
 
@@ -56,7 +85,12 @@ impl LoadingState<SCellImageInfo> {
             }
         });
 
-        AppInner::Loading(Self { buildkit, rx })
+        AppInner::Loading(Self {
+            buildkit,
+            rx,
+            prev: None,
+            watcher: None,
+        })
     }
 }
 
@@ -67,7 +101,12 @@ impl<Item: Clone + AppItemSuperTrait> LoadingState<Item> {
     /// - [`AppInner::Ls`] — stop succeeded; contains the refreshed item list
     pub fn try_recv(self) -> color_eyre::Result<AppInner<Item>> {
         match self.rx.recv_timeout(MIN_FPS) {
-            Ok(Ok(items)) => Ok(LsState::ls(items, self.buildkit)),
+            Ok(Ok(items)) => {
+                Ok(match self.prev {
+                    Some(prev) => prev.refresh(items),
+                    None => LsState::ls(items, self.watcher, self.buildkit),
+                })
+            },
             Ok(Err(e)) => {
                 color_eyre::eyre::bail!(e)
             },
@@ -1,12 +1,23 @@
 mod ui;
+mod view;
+mod watch;
+
+use std::collections::HashSet;
 
 use color_eyre::eyre::ContextCompat;
-use ratatui::widgets::TableState;
+use ratatui::{crossterm::event::KeyCode, widgets::TableState};
+pub use view::LsItem;
+pub use watch::Watcher;
 
 use crate::{
     buildkit::{BuildKitD, container_info::SCellContainerInfo, image_info::SCellImageInfo},
     cli::ls::app::{
-        AppInner, AppItemSuperTrait, confirm_remove::ConfirmRemoveState, inspect::InspectState,
+        AppInner, AppItemSuperTrait,
+        attaching::AttachingState,
+        confirm_remove::ConfirmRemoveState,
+        error_window::ErrorWindowState,
+        inspect::InspectState,
+        ls::{view::LsView, watch::LsUpdate},
         stopping::StoppingState,
     },
 };
@@ -14,33 +25,188 @@ use crate::{
 /// Holds the data for the interactive container table view.
 pub struct LsState<Item> {
     pub items: Vec<Item>,
+    /// Indices of the shown `items`, in the order they are shown.
+    pub rows: Vec<usize>,
+    pub view: LsView,
+    /// Keys of the items, marked for the bulk actions.
+    pub marked: HashSet<String>,
+    /// Selection over the shown `rows`.
     pub table_state: TableState,
+    /// Live updates of the items, if they are watched.
+    pub watcher: Option<Watcher<Item>>,
     pub buildkit: BuildKitD,
 }
 
 impl<Item: Clone + AppItemSuperTrait> LsState<Item> {
     pub fn ls(
         items: Vec<Item>,
+        watcher: Option<Watcher<Item>>,
         buildkit: BuildKitD,
     ) -> AppInner<Item> {
-        let mut table_state = TableState::default();
-        if !items.is_empty() {
-            table_state.select(Some(0));
-        }
-        AppInner::Ls(Self {
+        let mut ls_state = Self {
             items,
-            table_state,
+            rows: Vec::new(),
+            view: LsView::default(),
+            marked: HashSet::new(),
+            table_state: TableState::default(),
+            watcher,
             buildkit,
-        })
+        };
+        ls_state.update_rows(None);
+        AppInner::Ls(ls_state)
+    }
+
+    /// Replaces the items with the refreshed ones, preserving the filter, the sorting,
+    /// the marks and the selected item, if they are still present.
+    pub fn refresh(
+        self,
+        items: Vec<Item>,
+    ) -> AppInner<Item> {
+        AppInner::Ls(self.refreshed(items))
+    }
+
+    fn refreshed(
+        mut self,
+        items: Vec<Item>,
+    ) -> Self {
+        self.replace_items(items);
+        self
+    }
+
+    /// Applies the pending live updates of the items, preserving the filter, the sorting,
+    /// the marks and the selected item, if they are still present.
+    pub fn apply_updates(&mut self) {
+        let Some(updates) = self.watcher.as_ref().map(Watcher::updates) else {
+            return;
+        };
+        if updates.is_empty() {
+            return;
+        }
+        let mut items = self.items.clone();
+        for update in updates {
+            match update {
+                LsUpdate::Status(key, status) => {
+                    items
+                        .iter_mut()
+                        .filter(|item| item.key() == key)
+                        .for_each(|item| item.set_status(status));
+                },
+                LsUpdate::Items(new_items) => items = new_items,
+            }
+        }
+        self.replace_items(items);
+    }
+
+    fn replace_items(
+        &mut self,
+        items: Vec<Item>,
+    ) {
+        let selected = self.selected_item().map(LsItem::key);
+        self.items = items;
+        let keys: HashSet<_> = self.items.iter().map(LsItem::key).collect();
+        self.marked.retain(|key| keys.contains(key));
+        self.update_rows(selected.as_deref());
+    }
+
+    /// Finishes the bulk action: clears the marks and replaces the items with the
+    /// refreshed ones, showing the `errors` of the failed items, if any.
+    pub fn finish(
+        mut self,
+        items: color_eyre::Result<Vec<Item>>,
+        errors: Option<String>,
+    ) -> AppInner<Item> {
+        self.marked.clear();
+        let ls_state = match items {
+            Ok(items) => self.refreshed(items),
+            Err(e) => {
+                return AppInner::ErrorWindow(ErrorWindowState {
+                    ls_state: self,
+                    message: e.to_string(),
+                });
+            },
+        };
+        match errors {
+            Some(message) => AppInner::ErrorWindow(ErrorWindowState { ls_state, message }),
+            None => AppInner::Ls(ls_state),
+        }
+    }
+
+    /// Returns the currently selected item, if any.
+    pub fn selected_item(&self) -> Option<&Item> {
+        let row = self.rows.get(self.table_state.selected()?)?;
+        self.items.get(*row)
+    }
+
+    /// Returns the items, the bulk action is applied to: the shown marked ones or the
+    /// selected one, if none of the shown items is marked.
+    /// The marked items, hidden by the filter, are never acted on.
+    pub fn targets(&self) -> Vec<Item> {
+        let marked: Vec<_> = self
+            .shown_items()
+            .filter(|item| self.is_marked(item))
+            .cloned()
+            .collect();
+        if marked.is_empty() {
+            return self.selected_item().cloned().into_iter().collect();
+        }
+        marked
+    }
+
+    /// Returns the number of the marked items, hidden by the filter.
+    pub fn hidden_marked(&self) -> usize {
+        let shown = self
+            .shown_items()
+            .filter(|item| self.is_marked(item))
+            .count();
+        self.marked.len().saturating_sub(shown)
+    }
+
+    /// Returns `true` if the item is marked for the bulk actions.
+    pub fn is_marked(
+        &self,
+        item: &Item,
+    ) -> bool {
+        self.marked.contains(&item.key())
+    }
+
+    /// Toggles the mark of the selected item and moves the selection to the next row.
+    pub fn toggle_mark(&mut self) {
+        let Some(key) = self.selected_item().map(LsItem::key) else {
+            return;
+        };
+        if !self.marked.remove(&key) {
+            self.marked.insert(key);
+        }
+        self.next();
+    }
+
+    /// Marks all shown orphaned items.
+    pub fn mark_orphans(&mut self) {
+        let orphans: Vec<_> = self
+            .shown_items()
+            .filter(|item| item.orphan())
+            .map(LsItem::key)
+            .collect();
+        self.marked.extend(orphans);
+    }
+
+    /// Clears the marks.
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+    }
+
+    /// Returns the shown items, in the order they are shown.
+    pub fn shown_items(&self) -> impl Iterator<Item = &Item> {
+        self.rows.iter().filter_map(|i| self.items.get(*i))
     }
 
     /// Moves the table selection to the next row, wrapping to the top.
     pub fn next(&mut self) {
-        if self.items.is_empty() {
+        if self.rows.is_empty() {
             return;
         }
         let i = match self.table_state.selected() {
-            Some(i) if i != self.items.len().saturating_sub(1) => i.saturating_add(1),
+            Some(i) if i != self.rows.len().saturating_sub(1) => i.saturating_add(1),
             _ => 0,
         };
         self.table_state.select(Some(i));
@@ -48,78 +214,155 @@ impl<Item: Clone + AppItemSuperTrait> LsState<Item> {
 
     /// Moves the table selection to the previous row, wrapping to the bottom.
     pub fn previous(&mut self) {
-        if self.items.is_empty() {
+        if self.rows.is_empty() {
             return;
         }
         let i = match self.table_state.selected() {
             Some(i) if i != 0 => i.saturating_sub(1),
-            _ => self.items.len().saturating_sub(1),
+            _ => self.rows.len().saturating_sub(1),
         };
         self.table_state.select(Some(i));
     }
 
-    /// Shows confirmation dialog for removing the currently selected container.
+    /// Handles the keys changing the view: `/` starts editing the filter, `o` switches
+    /// to the next sort key, `O` toggles between the ascending and the descending
+    /// sort order, `Space` toggles the mark of the selected item and `A` marks all shown
+    /// orphaned items.
+    pub fn handle_view_key(
+        &mut self,
+        code: KeyCode,
+    ) {
+        match code {
+            KeyCode::Char('/') => self.view.filtering = true,
+            KeyCode::Char('o') => {
+                self.view.next_sort();
+                self.update_view();
+            },
+            KeyCode::Char('O') => {
+                self.view.descending = !self.view.descending;
+                self.update_view();
+            },
+            KeyCode::Char(' ') => self.toggle_mark(),
+            KeyCode::Char('A') => self.mark_orphans(),
+            _ => {},
+        }
+    }
+
+    /// Returns `true` if the filter is being edited.
+    pub fn is_filtering(&self) -> bool {
+        self.view.filtering
+    }
+
+    /// Handles keys while editing the filter, the shown rows are updated on each change.
+    /// `Enter` keeps the filter, `Esc` clears it.
+    pub fn handle_filter_key(
+        &mut self,
+        code: KeyCode,
+    ) {
+        match code {
+            KeyCode::Enter => self.view.filtering = false,
+            KeyCode::Esc => {
+                self.view.filtering = false;
+                self.clear_filter();
+            },
+            KeyCode::Backspace => {
+                self.view.filter.pop();
+                self.update_view();
+            },
+            KeyCode::Char(c) => {
+                self.view.filter.push(c);
+                self.update_view();
+            },
+            _ => {},
+        }
+    }
+
+    /// Clears the filter.
+    pub fn clear_filter(&mut self) {
+        if !self.view.filter.is_empty() {
+            self.view.filter.clear();
+            self.update_view();
+        }
+    }
+
+    /// Updates the shown rows after the view change, preserving the selected item.
+    fn update_view(&mut self) {
+        let selected = self.selected_item().map(LsItem::key);
+        self.update_rows(selected.as_deref());
+    }
+
+    /// Updates the shown rows, selecting the item with the `selected` key or the first
+    /// row, if it is not shown.
+    fn update_rows(
+        &mut self,
+        selected: Option<&str>,
+    ) {
+        self.rows = self.view.rows(&self.items);
+        let selected = selected
+            .and_then(|key| self.shown_items().position(|item| item.key() == key))
+            .or((!self.rows.is_empty()).then_some(0));
+        self.table_state.select(selected);
+    }
+
+    /// Shows confirmation dialog for removing the marked items or the currently selected
+    /// one.
     pub fn confirm_remove(self) -> color_eyre::Result<ConfirmRemoveState<Item>> {
-        let selected = self
-            .table_state
-            .selected()
-            .context("Some item in the list must be selected")?;
-        let selected_to_remove = self
-            .items
-            .get(selected)
-            .context("Selected item must be present in the list")?;
+        let selected_to_remove = self.targets();
+        color_eyre::eyre::ensure!(
+            !selected_to_remove.is_empty(),
+            "Some item in the list must be selected"
+        );
 
         Ok(ConfirmRemoveState {
-            selected_to_remove: selected_to_remove.clone(),
+            selected_to_remove,
             ls_state: self,
         })
     }
 }
 
 impl LsState<SCellContainerInfo> {
-    /// Returns a [`InspectState`] for the currently selected container.
+    /// Returns a [`InspectState`] for the currently selected container, following its
+    /// logs and resource stats.
     pub fn inspect(self) -> color_eyre::Result<AppInner<SCellContainerInfo>> {
-        let selected = self
-            .table_state
-            .selected()
-            .context("Some item in the list must be selected")?;
         let item = self
-            .items
-            .get(selected)
-            .context("Selected item must be present in the list")?
+            .selected_item()
+            .context("Some item in the list must be selected")?
             .clone();
-        InspectState::inspect(self, &item)
+        InspectState::inspect_live(self, &item)
     }
 
-    /// Initiates stopping of the currently selected container.
+    /// Initiates stopping of the marked containers or the currently selected one.
     ///
-    /// Spawns an async task that stops the container and then re-fetches
+    /// Spawns an async task that stops the containers concurrently and then re-fetches
     /// the full container list.
     pub fn stop_selected(self) -> color_eyre::Result<AppInner<SCellContainerInfo>> {
-        let selected = self
-            .table_state
-            .selected()
-            .context("Some item in the list must be selected")?;
+        let containers = self.targets();
+        color_eyre::eyre::ensure!(
+            !containers.is_empty(),
+            "Some item in the list must be selected"
+        );
+        Ok(StoppingState::stop(self, containers))
+    }
+
+    /// Initiates attaching to the currently selected container.
+    ///
+    /// Spawns an async task that starts the container, if it is not running, and
+    /// attaches to its shell.
+    pub fn attach_selected(self) -> color_eyre::Result<AppInner<SCellContainerInfo>> {
         let container = self
-            .items
-            .get(selected)
-            .context("Selected item must be present in the list")?
+            .selected_item()
+            .context("Some item in the list must be selected")?
             .clone();
-        Ok(StoppingState::stop(self, container))
+        Ok(AttachingState::attach(self, container))
     }
 }
 
 impl LsState<SCellImageInfo> {
     /// Returns a [`InspectState`] for the currently selected image.
     pub fn inspect(self) -> color_eyre::Result<AppInner<SCellImageInfo>> {
-        let selected = self
-            .table_state
-            .selected()
-            .context("Some item in the list must be selected")?;
         let item = self
-            .items
-            .get(selected)
-            .context("Selected item must be present in the list")?
+            .selected_item()
+            .context("Some item in the list must be selected")?
             .clone();
         InspectState::inspect(self, &item)
     }
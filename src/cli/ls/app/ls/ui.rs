@@ -1,11 +1,15 @@
 use ratatui::{
-    layout::{Constraint, Rect},
+    layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Cell, Row, StatefulWidget, Table, Widget},
 };
 
 use super::LsState;
-use crate::buildkit::{container_info::SCellContainerInfo, image_info::SCellImageInfo};
+use crate::{
+    buildkit::{container_info::SCellContainerInfo, image_info::SCellImageInfo},
+    cli::ls::app::ls::view::{LsView, SortKey},
+};
 
 impl Widget for &LsState<SCellContainerInfo> {
     fn render(
@@ -15,16 +19,18 @@ impl Widget for &LsState<SCellContainerInfo> {
     ) where
         Self: Sized,
     {
-        let header_cells = ["ID", "Target", "Blueprint Location", "Created At", "Status"]
-            .iter()
-            .map(|h| Cell::from(*h).style(Style::default().fg(Color::Cyan)));
-        let header = Row::new(header_cells)
-            .style(Style::default().add_modifier(Modifier::BOLD))
-            .height(1);
+        let area = render_filter(&self.view, self.rows.len(), self.items.len(), area, buf);
+        let header = header(&self.view);
 
-        let rows = self.items.iter().map(|c| {
+        let rows = self.shown_items().map(|c| {
             let cells = vec![
-                Cell::from(c.id.to_string()),
+                // Service containers are grouped under their main container
+                Cell::from(mark(
+                    self.is_marked(c),
+                    c.service_name
+                        .as_ref()
+                        .map_or_else(|| c.id.to_string(), |s| format!("  └ {s}")),
+                )),
                 Cell::from(
                     c.target
                         .as_ref()
@@ -45,7 +51,9 @@ impl Widget for &LsState<SCellContainerInfo> {
                     c.status.to_string()
                 }),
             ];
-            Row::new(cells).height(1)
+            Row::new(cells)
+                .style(marked_style(self.is_marked(c)))
+                .height(1)
         });
 
         let widths = [
@@ -77,16 +85,12 @@ impl Widget for &LsState<SCellImageInfo> {
     ) where
         Self: Sized,
     {
-        let header_cells = ["ID", "Target", "Blueprint Location", "Created At", "Status"]
-            .iter()
-            .map(|h| Cell::from(*h).style(Style::default().fg(Color::Cyan)));
-        let header = Row::new(header_cells)
-            .style(Style::default().add_modifier(Modifier::BOLD))
-            .height(1);
+        let area = render_filter(&self.view, self.rows.len(), self.items.len(), area, buf);
+        let header = header(&self.view);
 
-        let rows = self.items.iter().map(|c| {
+        let rows = self.shown_items().map(|c| {
             let cells = vec![
-                Cell::from(c.id.to_string()),
+                Cell::from(mark(self.is_marked(c), c.id.to_string())),
                 Cell::from(
                     c.target
                         .as_ref()
@@ -103,7 +107,9 @@ impl Widget for &LsState<SCellImageInfo> {
                 )),
                 Cell::from(if c.orphan { "orphan" } else { "-" }),
             ];
-            Row::new(cells).height(1)
+            Row::new(cells)
+                .style(marked_style(self.is_marked(c)))
+                .height(1)
         });
 
         let widths = [
@@ -126,3 +132,73 @@ impl Widget for &LsState<SCellImageInfo> {
         StatefulWidget::render(table, area, buf, &mut self.table_state.clone());
     }
 }
+
+/// Prefixes the ID of the marked item with the check mark.
+fn mark(
+    marked: bool,
+    id: String,
+) -> String {
+    if marked { format!("✓ {id}") } else { id }
+}
+
+fn marked_style(marked: bool) -> Style {
+    if marked {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    }
+}
+
+/// Returns the table header, marking the column the rows are sorted by.
+fn header(view: &LsView) -> Row<'static> {
+    let order = if view.descending { " ▼" } else { " ▲" };
+    let column = |title: &'static str, key: Option<SortKey>| {
+        let title = if key.is_some() && key == view.sort {
+            format!("{title}{order}")
+        } else {
+            title.to_string()
+        };
+        Cell::from(title).style(Style::default().fg(Color::Cyan))
+    };
+    Row::new([
+        column("ID", None),
+        column("Target", None),
+        column("Blueprint Location", Some(SortKey::Location)),
+        column("Created At", Some(SortKey::CreatedAt)),
+        column("Status", Some(SortKey::Status)),
+    ])
+    .style(Style::default().add_modifier(Modifier::BOLD))
+    .height(1)
+}
+
+/// Renders the filter line at the bottom of the area, if the filter is set or being
+/// edited, returning the rest of the area.
+fn render_filter(
+    view: &LsView,
+    shown: usize,
+    total: usize,
+    area: Rect,
+    buf: &mut ratatui::prelude::Buffer,
+) -> Rect {
+    if !view.filtering && view.filter.is_empty() {
+        return area;
+    }
+    let [table_area, filter_area] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+    let mut spans = vec![
+        Span::styled("/", Style::default().fg(Color::Yellow)),
+        Span::raw(view.filter.clone()),
+    ];
+    if view.filtering {
+        spans.push(Span::styled(
+            " ",
+            Style::default().add_modifier(Modifier::REVERSED),
+        ));
+    }
+    spans.push(Span::styled(
+        format!("  ({shown} of {total})"),
+        Style::default().fg(Color::DarkGray),
+    ));
+    Line::from(spans).render(filter_area, buf);
+    table_area
+}
@@ -0,0 +1,304 @@
+//! Filtering, sorting and grouping of the listed items.
+
+use std::{cmp::Ordering, collections::HashMap, path::Path};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    buildkit::{
+        container_info::{SCellContainerInfo, Status},
+        image_info::SCellImageInfo,
+    },
+    scell::{name::SCellId, types::name::TargetName},
+};
+
+/// Item of the `ls` table, which could be filtered, sorted and grouped.
+pub trait LsItem {
+    /// Unique key of the item, used to preserve the selection across refreshes.
+    fn key(&self) -> String;
+    /// 'Shell-Cell' ID, the items are grouped by.
+    fn group(&self) -> &SCellId;
+    /// Returns `true` if the item is shown under the main item of its group.
+    fn is_grouped(&self) -> bool;
+    fn target(&self) -> Option<&TargetName>;
+    fn location(&self) -> Option<&Path>;
+    fn created_at(&self) -> Option<DateTime<Utc>>;
+    fn status(&self) -> Status;
+    /// Updates the status, reported by the live updates.
+    fn set_status(
+        &mut self,
+        status: Status,
+    );
+    fn orphan(&self) -> bool;
+}
+
+impl LsItem for SCellContainerInfo {
+    fn key(&self) -> String {
+        Self::container_name(&self.id, self.service_name.as_ref())
+    }
+
+    fn group(&self) -> &SCellId {
+        &self.id
+    }
+
+    fn is_grouped(&self) -> bool {
+        self.service_name.is_some()
+    }
+
+    fn target(&self) -> Option<&TargetName> {
+        self.target.as_ref()
+    }
+
+    fn location(&self) -> Option<&Path> {
+        self.location.as_deref()
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn status(&self) -> Status {
+        self.status
+    }
+
+    fn set_status(
+        &mut self,
+        status: Status,
+    ) {
+        self.status = status;
+    }
+
+    fn orphan(&self) -> bool {
+        self.orphan
+    }
+}
+
+impl LsItem for SCellImageInfo {
+    fn key(&self) -> String {
+        self.id.to_string()
+    }
+
+    fn group(&self) -> &SCellId {
+        &self.id
+    }
+
+    fn is_grouped(&self) -> bool {
+        false
+    }
+
+    fn target(&self) -> Option<&TargetName> {
+        self.target.as_ref()
+    }
+
+    fn location(&self) -> Option<&Path> {
+        self.location.as_deref()
+    }
+
+    fn created_at(&self) -> Option<DateTime<Utc>> {
+        self.created_at
+    }
+
+    fn status(&self) -> Status {
+        Status::Empty
+    }
+
+    /// Images have no status, so it is never updated.
+    fn set_status(
+        &mut self,
+        _status: Status,
+    ) {
+    }
+
+    fn orphan(&self) -> bool {
+        self.orphan
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    CreatedAt,
+    Status,
+    Location,
+}
+
+impl SortKey {
+    fn cmp<Item: LsItem>(
+        self,
+        a: &Item,
+        b: &Item,
+    ) -> Ordering {
+        match self {
+            Self::CreatedAt => a.created_at().cmp(&b.created_at()),
+            Self::Status => {
+                a.status()
+                    .cmp(&b.status())
+                    .then_with(|| a.orphan().cmp(&b.orphan()))
+            },
+            Self::Location => a.location().cmp(&b.location()),
+        }
+    }
+}
+
+/// Filter, sort and grouping settings of the `ls` table.
+#[derive(Debug, Default, Clone)]
+pub struct LsView {
+    /// Case insensitive pattern, the item's ID, target or location must contain.
+    pub filter: String,
+    /// The filter is being edited.
+    pub filtering: bool,
+    /// `None` keeps the order, the items were listed in.
+    pub sort: Option<SortKey>,
+    pub descending: bool,
+}
+
+impl LsView {
+    /// Switches to the next sort key, returning to the listed order after the last one.
+    pub fn next_sort(&mut self) {
+        self.sort = match self.sort {
+            None => Some(SortKey::CreatedAt),
+            Some(SortKey::CreatedAt) => Some(SortKey::Status),
+            Some(SortKey::Status) => Some(SortKey::Location),
+            Some(SortKey::Location) => None,
+        };
+    }
+
+    /// Returns the indices of the items to show, in the order to show them.
+    /// Grouped items (service containers) follow the main item of their group and groups
+    /// are sorted by their main item (or by the first one, if the main item is missing).
+    pub fn rows<Item: LsItem>(
+        &self,
+        items: &[Item],
+    ) -> Vec<usize> {
+        let mut rows: Vec<(usize, &Item)> = items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| self.matches(*item))
+            .collect();
+
+        let mut heads: HashMap<&SCellId, (usize, &Item)> = HashMap::new();
+        for &(i, item) in &rows {
+            let head = heads.entry(item.group()).or_insert((i, item));
+            if head.1.is_grouped() && !item.is_grouped() {
+                *head = (i, item);
+            }
+        }
+
+        rows.sort_by(|&(a_i, a), &(b_i, b)| {
+            let (a_head_i, a_head) = heads.get(a.group()).copied().unwrap_or((a_i, a));
+            let (b_head_i, b_head) = heads.get(b.group()).copied().unwrap_or((b_i, b));
+            let ord = self
+                .sort
+                .map_or(Ordering::Equal, |key| key.cmp(a_head, b_head));
+            let ord = if self.descending { ord.reverse() } else { ord };
+            ord.then_with(|| a_head_i.cmp(&b_head_i))
+                .then_with(|| a.is_grouped().cmp(&b.is_grouped()))
+                .then_with(|| a_i.cmp(&b_i))
+        });
+        rows.into_iter().map(|(i, _)| i).collect()
+    }
+
+    fn matches(
+        &self,
+        item: &impl LsItem,
+    ) -> bool {
+        if self.filter.is_empty() {
+            return true;
+        }
+        let filter = self.filter.to_lowercase();
+        let contains = |s: &str| s.to_lowercase().contains(&filter);
+        contains(item.group().as_str())
+            || item.target().is_some_and(|t| contains(&t.to_string()))
+            || item
+                .location()
+                .is_some_and(|l| contains(&l.display().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::cli::ls::test_utils;
+
+    fn container(
+        id: &str,
+        service_name: Option<&str>,
+        location: &str,
+        status: Status,
+        created_at: i64,
+    ) -> SCellContainerInfo {
+        SCellContainerInfo {
+            service_name: service_name.map(|s| s.parse().unwrap()),
+            location: Some(location.into()),
+            created_at: DateTime::from_timestamp_secs(created_at),
+            ..test_utils::container(id, status)
+        }
+    }
+
+    fn items() -> Vec<SCellContainerInfo> {
+        vec![
+            container("scell-b", Some("db"), "/b", Status::Running, 2),
+            container("scell-a", None, "/a", Status::Running, 3),
+            container("scell-b", None, "/b", Status::Exited, 1),
+            container("scell-c", None, "/c", Status::Created, 4),
+            container("scell-a", Some("cache"), "/a", Status::Running, 3),
+        ]
+    }
+
+    #[test_case(&LsView::default(), &[1, 4, 2, 0, 3] ; "listed order, grouped")]
+    #[test_case(
+        &LsView { sort: Some(SortKey::CreatedAt), ..Default::default() },
+        &[2, 0, 1, 4, 3]
+        ; "created at"
+    )]
+    #[test_case(
+        &LsView { sort: Some(SortKey::CreatedAt), descending: true, ..Default::default() },
+        &[3, 1, 4, 2, 0]
+        ; "created at descending"
+    )]
+    #[test_case(
+        &LsView { sort: Some(SortKey::Status), ..Default::default() },
+        &[3, 1, 4, 2, 0]
+        ; "status"
+    )]
+    #[test_case(
+        &LsView { sort: Some(SortKey::Location), descending: true, ..Default::default() },
+        &[3, 2, 0, 1, 4]
+        ; "location descending"
+    )]
+    #[test_case(
+        &LsView { filter: "/A".to_string(), ..Default::default() },
+        &[1, 4]
+        ; "filter by location"
+    )]
+    #[test_case(
+        &LsView { filter: "scell-c".to_string(), ..Default::default() },
+        &[3]
+        ; "filter by id"
+    )]
+    #[test_case(
+        &LsView { filter: "other".to_string(), ..Default::default() },
+        &[]
+        ; "filter nothing"
+    )]
+    fn rows_test(
+        view: &LsView,
+        expected: &[usize],
+    ) {
+        assert_eq!(view.rows(&items()), expected);
+    }
+
+    #[test]
+    fn next_sort_test() {
+        let mut view = LsView::default();
+        view.next_sort();
+        assert_eq!(view.sort, Some(SortKey::CreatedAt));
+        view.next_sort();
+        assert_eq!(view.sort, Some(SortKey::Status));
+        view.next_sort();
+        assert_eq!(view.sort, Some(SortKey::Location));
+        view.next_sort();
+        assert_eq!(view.sort, None);
+    }
+}
@@ -0,0 +1,63 @@
+//! Live updates of the listed items.
+
+use std::sync::mpsc::Receiver;
+
+use futures::StreamExt;
+
+use crate::buildkit::{
+    BuildKitD,
+    container_info::{SCellContainerEvent, SCellContainerInfo, Status},
+};
+
+pub enum LsUpdate<Item> {
+    /// The item with the key has changed its status.
+    Status(String, Status),
+    /// The items list is re-fetched.
+    Items(Vec<Item>),
+}
+
+/// Watches the changes of the listed items in the background, until it is dropped.
+pub struct Watcher<Item> {
+    rx: Receiver<LsUpdate<Item>>,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+impl Watcher<SCellContainerInfo> {
+    /// Spawns an async task that subscribes to the Docker events of the 'Shell-Cell'
+    /// containers, re-fetching the list only if some container is created or removed.
+    /// The task stops, if the events stream fails.
+    pub fn containers(buildkit: BuildKitD) -> Self {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let handle = tokio::spawn(async move {
+            let mut events = std::pin::pin!(buildkit.container_events());
+            while let Some(Ok(event)) = events.next().await {
+                let update = match event {
+                    SCellContainerEvent::Status(name, status) => LsUpdate::Status(name, status),
+                    SCellContainerEvent::Changed => {
+                        let Ok(items) = buildkit.list_containers().await else {
+                            break;
+                        };
+                        LsUpdate::Items(items)
+                    },
+                };
+                if tx.send(update).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { rx, handle }
+    }
+}
+
+impl<Item> Watcher<Item> {
+    /// Returns the pending updates, without blocking.
+    pub fn updates(&self) -> Vec<LsUpdate<Item>> {
+        self.rx.try_iter().collect()
+    }
+}
+
+impl<Item> Drop for Watcher<Item> {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
@@ -1,9 +1,11 @@
+mod attaching;
 mod confirm_remove;
 mod error_window;
 mod help_window;
 mod inspect;
 mod loading;
 mod ls;
+mod progress;
 mod removing;
 mod stopping;
 mod ui;
@@ -15,12 +17,13 @@ use crate::{
     cli::{
         MIN_FPS,
         ls::app::{
+            attaching::{AttachedState, AttachingState},
             confirm_remove::ConfirmRemoveState,
             error_window::ErrorWindowState,
             help_window::HelpWindowState,
             inspect::{InspectState, ItemToInspect},
             loading::LoadingState,
-            ls::LsState,
+            ls::{LsItem, LsState},
             removing::RemovingState,
             stopping::StoppingState,
         },
@@ -32,17 +35,26 @@ use crate::{
 ///
 /// Transitions:
 /// - `Loading` → `Ls` (once container list is fetched)
-/// - `Ls` → `Stopping` (user presses `s` on a selected container)
-/// - `Ls` → `ConfirmRemove` (user presses `r` on a selected container)
+/// - `Ls` → `Ls` (container statuses are updated live, as Docker reports the changes)
+/// - `Ls` → `Stopping` (user presses `s` on the marked or selected containers)
+/// - `Ls` → `ConfirmRemove` (user presses `r` on the marked or selected items)
 /// - `Ls` → `Help` (user presses `h`)
+/// - `Ls` → `Ls` (user filters with `/`, sorts with `o`/`O`, marks with `Space`/`A` or
+///   clears the filter and the marks with `Esc`)
 /// - `Ls` → `ShowDefinition` (user presses `i` on a selected item)
+/// - `Ls` → `Attaching` (user presses `Enter` or `a` on a selected container)
+/// - `Attaching` → `Attached` (once the container is started and its shell is attached)
+/// - `Attaching` → `Error` (start or attach operation fails)
+/// - `Attached` → `Loading` (once the shell session is finished; triggers a list refresh)
 /// - `ConfirmRemove` → `Removing` (user confirms with `y`)
 /// - `ConfirmRemove` → `Ls` (user cancels with `n` or `Esc`)
-/// - `Stopping` → `Loading` (once the item is stopped; triggers a list refresh)
-/// - `Stopping` → `Error` (stop operation fails)
-/// - `Removing` → `Loading` (once the item is removed; triggers a list refresh)
-/// - `Removing` → `Error` (remove operation fails)
+/// - `Stopping` → `Ls` (once the items are stopped and the list is refreshed)
+/// - `Stopping` → `Error` (stop operation fails for some items)
+/// - `Removing` → `Ls` (once the items are removed and the list is refreshed)
+/// - `Removing` → `Error` (remove operation fails for some items)
 /// - `Error` → `Ls` (user presses `Esc`)
+/// - `ShowDefinition` → `ShowDefinition` (user switches between the definition, logs and
+///   stats tabs of a container with `Tab`)
 /// - `ShowDefinition` → `Ls` (user presses `i` or `Esc`)
 /// - Any state → `Exit` (user presses `Ctrl-C` or `Ctrl-D`)
 pub enum App {
@@ -50,8 +62,8 @@ pub enum App {
     Images(AppInner<SCellImageInfo>),
 }
 
-pub trait AppItemSuperTrait: ItemToInspect {}
-impl<T: ItemToInspect> AppItemSuperTrait for T {}
+pub trait AppItemSuperTrait: ItemToInspect + LsItem {}
+impl<T: ItemToInspect + LsItem> AppItemSuperTrait for T {}
 
 pub enum AppInner<Item: AppItemSuperTrait> {
     /// Fetching the item list from Docker in the background.
@@ -60,23 +72,27 @@ pub enum AppInner<Item: AppItemSuperTrait> {
     Ls(LsState<Item>),
     /// Displaying the help overlay over the item table.
     HelpWindow(HelpWindowState<Item>),
-    /// Stopping a selected item and refreshing the list.
+    /// Stopping the marked or selected items and refreshing the list.
     Stopping(StoppingState<Item>),
-    /// Confirming removal of a selected item.
+    /// Confirming removal of the marked or selected items.
     ConfirmRemove(ConfirmRemoveState<Item>),
-    /// Removing a selected item and refreshing the list.
+    /// Removing the marked or selected items and refreshing the list.
     Removing(RemovingState<Item>),
     /// Displaying an error that occurred during a background operation.
     ErrorWindow(ErrorWindowState<Item>),
     /// Displaying the definition overlay for the selected item.
     Inspect(InspectState<Item>),
+    /// Starting a selected item, if needed, and attaching to its shell.
+    Attaching(AttachingState<Item>),
+    /// Running the shell session of the attached item, replacing the whole TUI.
+    Attached(Box<AttachedState<Item>>),
     /// Terminal state — the event loop exits.
     Exit,
 }
 
 impl App {
     /// Runs the TUI event loop, polling for state transitions and key events.
-    pub fn run(
+    pub async fn run(
         buildkit: &BuildKitD,
         terminal: &mut Terminal,
     ) -> color_eyre::Result<()> {
@@ -95,6 +111,19 @@ impl App {
             };
             app = new_app;
 
+            // The shell session runs its own event loop, returning to the refreshed list
+            // once it is finished
+            if let Self::Containers(AppInner::Attached(state)) = app {
+                let AttachedState {
+                    attached,
+                    pty,
+                    ls_state,
+                } = *state;
+                crate::cli::run::attach(buildkit, pty, &attached, terminal).await?;
+                app = Self::Containers(LoadingState::<SCellContainerInfo>::reload(ls_state));
+                continue;
+            }
+
             match &mut app {
                 Self::Containers(app) => {
                     terminal.draw(|f| {
@@ -120,7 +149,7 @@ impl App {
     ) -> color_eyre::Result<Self> {
         if event::poll(MIN_FPS)?
             && let Event::Key(key) = event::read()?
-            && key.kind == KeyEventKind::Press
+            && key.kind != KeyEventKind::Release
         {
             match self {
                 Self::Containers(app) => {
@@ -162,6 +191,18 @@ impl<Item: Clone + AppItemSuperTrait> AppInner<Item> {
             self = state.try_recv()?;
         }
 
+        if let Self::Attaching(state) = self {
+            self = state.try_recv()?;
+        }
+
+        if let Self::Ls(ref mut state) = self {
+            state.apply_updates();
+        }
+
+        if let Self::Inspect(ref mut state) = self {
+            state.update();
+        }
+
         if matches!(self, Self::Exit) {
             return Ok(None);
         }
@@ -173,10 +214,20 @@ impl<Item: Clone + AppItemSuperTrait> AppInner<Item> {
 impl AppInner<SCellContainerInfo> {
     /// Handles a single key event, dispatching navigation and actions
     /// based on the current state.
+    #[allow(clippy::too_many_lines)]
     fn handle_key_event(
         mut self,
         key: KeyEvent,
     ) -> color_eyre::Result<Option<Self>> {
+        // Keys edit the filter, while it is being edited
+        if let Self::Ls(ref mut ls_state) = self
+            && ls_state.is_filtering()
+            && !key.modifiers.contains(event::KeyModifiers::CONTROL)
+        {
+            ls_state.handle_filter_key(key.code);
+            return Ok(Some(self));
+        }
+
         match key.code {
             KeyCode::Char('q') => {
                 if let Self::Ls(_) = self {
@@ -203,6 +254,11 @@ impl AppInner<SCellContainerInfo> {
                     _ => {},
                 }
             },
+            KeyCode::Tab => {
+                if let Self::Inspect(ref mut inspect_state) = self {
+                    inspect_state.next_tab();
+                }
+            },
             KeyCode::Down | KeyCode::Char('j') => {
                 if let Self::Ls(ref mut ls_state) = self {
                     ls_state.next();
@@ -224,6 +280,16 @@ impl AppInner<SCellContainerInfo> {
                     self = ls_state.stop_selected()?;
                 }
             },
+            KeyCode::Enter | KeyCode::Char('a') => {
+                if let Self::Ls(ls_state) = self {
+                    self = ls_state.attach_selected()?;
+                }
+            },
+            KeyCode::Char('/' | 'o' | 'O' | ' ' | 'A') => {
+                if let Self::Ls(ref mut ls_state) = self {
+                    ls_state.handle_view_key(key.code);
+                }
+            },
             KeyCode::Char('r') => {
                 if let Self::Ls(ls_state) = self {
                     self = Self::ConfirmRemove(ls_state.confirm_remove()?);
@@ -247,6 +313,11 @@ impl AppInner<SCellContainerInfo> {
                     Self::ConfirmRemove(confirm_state) => {
                         self = confirm_state.cancel();
                     },
+                    Self::Ls(mut ls_state) => {
+                        ls_state.clear_filter();
+                        ls_state.clear_marks();
+                        self = Self::Ls(ls_state);
+                    },
                     _ => {},
                 }
             },
@@ -264,6 +335,15 @@ impl AppInner<SCellImageInfo> {
         mut self,
         key: KeyEvent,
     ) -> color_eyre::Result<Option<Self>> {
+        // Keys edit the filter, while it is being edited
+        if let Self::Ls(ref mut ls_state) = self
+            && ls_state.is_filtering()
+            && !key.modifiers.contains(event::KeyModifiers::CONTROL)
+        {
+            ls_state.handle_filter_key(key.code);
+            return Ok(Some(self));
+        }
+
         match key.code {
             KeyCode::Char('q') => {
                 if let Self::Ls(_) = self {
@@ -306,6 +386,11 @@ impl AppInner<SCellImageInfo> {
                     inspect_state.scroll_up();
                 }
             },
+            KeyCode::Char('/' | 'o' | 'O' | ' ' | 'A') => {
+                if let Self::Ls(ref mut ls_state) = self {
+                    ls_state.handle_view_key(key.code);
+                }
+            },
             KeyCode::Char('r') => {
                 if let Self::Ls(ls_state) = self {
                     self = Self::ConfirmRemove(ls_state.confirm_remove()?);
@@ -329,6 +414,11 @@ impl AppInner<SCellImageInfo> {
                     Self::ConfirmRemove(confirm_state) => {
                         self = confirm_state.cancel();
                     },
+                    Self::Ls(mut ls_state) => {
+                        ls_state.clear_filter();
+                        ls_state.clear_marks();
+                        self = Self::Ls(ls_state);
+                    },
                     _ => {},
                 }
             },
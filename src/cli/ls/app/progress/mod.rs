@@ -0,0 +1,125 @@
+mod ui;
+
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+
+pub use ui::render_progress;
+
+use crate::cli::MIN_FPS;
+
+enum ProgressUpdate<Item> {
+    /// The action on the item with the index is finished.
+    Item(usize, color_eyre::Result<()>),
+    /// The actions on all items are finished and the items list is re-fetched.
+    Finished(color_eyre::Result<Vec<Item>>),
+}
+
+/// Per-item progress of the action, applied concurrently to the items in the background.
+pub struct Progress<Item> {
+    /// The items with the results of the action, `None` while it is in progress.
+    pub results: Vec<(Item, Option<Result<(), String>>)>,
+    rx: Receiver<ProgressUpdate<Item>>,
+}
+
+impl<Item: Send + 'static> Progress<Item> {
+    /// Spawns a background task that runs the `actions` (one per item) concurrently
+    /// and then re-fetches the items list with `refresh`.
+    pub fn spawn<Action, Refresh>(
+        items: Vec<Item>,
+        actions: Vec<Action>,
+        refresh: Refresh,
+    ) -> Self
+    where
+        Action: Future<Output = color_eyre::Result<()>> + Send + 'static,
+        Refresh: Future<Output = color_eyre::Result<Vec<Item>>> + Send + 'static,
+    {
+        let (tx, rx) = std::sync::mpsc::channel();
+        tokio::spawn(async move {
+            futures::future::join_all(actions.into_iter().enumerate().map(|(i, action)| {
+                let tx = tx.clone();
+                async move {
+                    let res = action.await;
+                    drop(tx.send(ProgressUpdate::Item(i, res)));
+                }
+            }))
+            .await;
+            drop(tx.send(ProgressUpdate::Finished(refresh.await)));
+        });
+        Self {
+            results: items.into_iter().map(|item| (item, None)).collect(),
+            rx,
+        }
+    }
+}
+
+impl<Item> Progress<Item> {
+    /// Polls the background task for the progress updates. Returns the re-fetched items
+    /// list once all actions are finished.
+    pub fn try_recv(&mut self) -> color_eyre::Result<Option<color_eyre::Result<Vec<Item>>>> {
+        loop {
+            match self.rx.recv_timeout(MIN_FPS) {
+                Ok(ProgressUpdate::Item(i, res)) => {
+                    if let Some((_, result)) = self.results.get_mut(i) {
+                        *result = Some(res.map_err(|e| e.to_string()));
+                    }
+                },
+                Ok(ProgressUpdate::Finished(items)) => return Ok(Some(items)),
+                Err(RecvTimeoutError::Timeout) => return Ok(None),
+                Err(RecvTimeoutError::Disconnected) => {
+                    color_eyre::eyre::bail!(
+                        "Progress channel cannot be disconnected without returning a result"
+                    )
+                },
+            }
+        }
+    }
+
+    /// Returns the error message, listing all failed items, if any.
+    pub fn errors(
+        &self,
+        name: impl Fn(&Item) -> String,
+    ) -> Option<String> {
+        let errors: Vec<_> = self
+            .results
+            .iter()
+            .filter_map(|(item, result)| {
+                match result {
+                    Some(Err(e)) => Some(format!("'{}': {e}", name(item))),
+                    _ => None,
+                }
+            })
+            .collect();
+        (!errors.is_empty()).then(|| errors.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn progress_test() {
+        let actions: Vec<futures::future::BoxFuture<'static, color_eyre::Result<()>>> = vec![
+            Box::pin(async { Ok(()) }),
+            Box::pin(async { color_eyre::eyre::bail!("cannot stop") }),
+            Box::pin(async { Ok(()) }),
+        ];
+        let mut progress =
+            Progress::spawn(vec!["a", "b", "c"], actions, async { Ok(vec!["a", "c"]) });
+
+        let items = loop {
+            if let Some(items) = progress.try_recv().unwrap() {
+                break items.unwrap();
+            }
+        };
+        assert_eq!(items, vec!["a", "c"]);
+        assert_eq!(progress.results, vec![
+            ("a", Some(Ok(()))),
+            ("b", Some(Err("cannot stop".to_string()))),
+            ("c", Some(Ok(()))),
+        ]);
+        assert_eq!(
+            progress.errors(ToString::to_string),
+            Some("'b': cannot stop".to_string())
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::cli::ls::app::progress::Progress;
+
+/// Renders the popup with the action's progress, one line per item.
+#[allow(clippy::indexing_slicing)]
+pub fn render_progress<Item>(
+    progress: &Progress<Item>,
+    action: &str,
+    color: Color,
+    name: impl Fn(&Item) -> String,
+    area: Rect,
+    buf: &mut ratatui::prelude::Buffer,
+) {
+    let total = progress.results.len();
+    let completed = progress
+        .results
+        .iter()
+        .filter(|(_, result)| result.is_some())
+        .count();
+
+    // header, an empty line, the items and the borders
+    let height = u16::try_from(total)
+        .unwrap_or(u16::MAX)
+        .saturating_add(4)
+        .min(area.height);
+    let vertical = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(height),
+        Constraint::Fill(1),
+    ])
+    .split(area);
+
+    let horizontal = Layout::horizontal([
+        Constraint::Percentage(20),
+        Constraint::Percentage(60),
+        Constraint::Percentage(20),
+    ])
+    .split(vertical[1]);
+
+    let mut text = vec![
+        Line::from(vec![
+            Span::styled(
+                action.to_string(),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("... [{completed}/{total}]"),
+                Style::default().fg(color),
+            ),
+        ]),
+        Line::from(""),
+    ];
+    text.extend(progress.results.iter().map(|(item, result)| {
+        let (icon, style) = match result {
+            None => ("◌", Style::default().fg(Color::Gray)),
+            Some(Ok(())) => ("✓", Style::default().fg(Color::Green)),
+            Some(Err(_)) => ("✗", Style::default().fg(Color::Red)),
+        };
+        Line::from(Span::styled(format!("{icon} {}", name(item)), style))
+    }));
+
+    Widget::render(Clear, horizontal[1], buf);
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color)),
+        )
+        .centered();
+
+    paragraph.render(horizontal[1], buf);
+}
@@ -1,77 +1,68 @@
 mod ui;
 
-use std::sync::mpsc::{Receiver, RecvTimeoutError};
-
 use crate::{
     buildkit::{container_info::SCellContainerInfo, image_info::SCellImageInfo},
-    cli::{
-        MIN_FPS,
-        ls::app::{AppInner, AppItemSuperTrait, error_window::ErrorWindowState, ls::LsState},
+    cli::ls::app::{
+        AppInner, AppItemSuperTrait,
+        ls::{LsItem, LsState},
+        progress::Progress,
     },
 };
 
-/// Holds the state while a item is being removed in the background.
+/// Holds the state while the items are being removed in the background.
 ///
-/// The spawned task removes the item and then re-fetches
-/// the full item's list, sending the result back over the channel.
+/// The spawned task removes the items concurrently and then re-fetches
+/// the full item's list, sending the per-item results and the list back over the
+/// channel.
 pub struct RemovingState<Item> {
-    pub for_removal: Item,
+    pub progress: Progress<Item>,
     pub ls_state: LsState<Item>,
-    pub rx: Receiver<color_eyre::Result<Vec<Item>>>,
 }
 
 impl RemovingState<SCellContainerInfo> {
-    /// Spawns a background task that removes `container` and re-fetches the list,
-    /// returning a [`RemovingState`] to track progress.
+    /// Spawns a background task that removes `for_removal` containers and re-fetches
+    /// the list, returning a [`RemovingState`] to track progress.
     pub fn remove(
         ls_state: LsState<SCellContainerInfo>,
-        for_removal: SCellContainerInfo,
+        for_removal: Vec<SCellContainerInfo>,
     ) -> AppInner<SCellContainerInfo> {
         let buildkit = ls_state.buildkit.clone();
-        let (tx, rx) = std::sync::mpsc::channel();
-        tokio::spawn({
-            let for_removal = for_removal.clone();
-            async move {
-                let res = buildkit.cleanup_container(&for_removal).await;
-                let res = match res {
-                    Ok(()) => buildkit.list_containers().await,
-                    Err(e) => Err(e),
-                };
-                drop(tx.send(res));
-            }
-        });
+        let actions = for_removal
+            .iter()
+            .cloned()
+            .map(|container| {
+                let buildkit = buildkit.clone();
+                async move { buildkit.cleanup_container(&container).await }
+            })
+            .collect();
+        let refresh = async move { buildkit.list_containers().await };
         AppInner::Removing(Self {
-            for_removal,
+            progress: Progress::spawn(for_removal, actions, refresh),
             ls_state,
-            rx,
         })
     }
 }
 
 impl RemovingState<SCellImageInfo> {
-    /// Spawns a background task that removes `image` and re-fetches the list,
-    /// returning a [`RemovingState`] to track progress.
+    /// Spawns a background task that removes `for_removal` images and re-fetches the
+    /// list, returning a [`RemovingState`] to track progress.
     pub fn remove(
         ls_state: LsState<SCellImageInfo>,
-        for_removal: SCellImageInfo,
+        for_removal: Vec<SCellImageInfo>,
     ) -> AppInner<SCellImageInfo> {
         let buildkit = ls_state.buildkit.clone();
-        let (tx, rx) = std::sync::mpsc::channel();
-        tokio::spawn({
-            let for_removal = for_removal.clone();
-            async move {
-                let res = buildkit.cleanup_image(&for_removal).await;
-                let res = match res {
-                    Ok(()) => buildkit.list_images().await,
-                    Err(e) => Err(e),
-                };
-                drop(tx.send(res));
-            }
-        });
+        let actions = for_removal
+            .iter()
+            .cloned()
+            .map(|image| {
+                let buildkit = buildkit.clone();
+                async move { buildkit.cleanup_image(&image).await }
+            })
+            .collect();
+        let refresh = async move { buildkit.list_images().await };
         AppInner::Removing(Self {
-            for_removal,
+            progress: Progress::spawn(for_removal, actions, refresh),
             ls_state,
-            rx,
         })
     }
 }
@@ -81,21 +72,14 @@ impl<Item: Clone + AppItemSuperTrait> RemovingState<Item> {
     ///
     /// - [`AppInner::Removing`] — still waiting (self is returned back)
     /// - [`AppInner::Ls`] — removal succeeded; contains the refreshed item list
-    pub fn try_recv(self) -> color_eyre::Result<AppInner<Item>> {
-        match self.rx.recv_timeout(MIN_FPS) {
-            Ok(Ok(items)) => Ok(LsState::ls(items, self.ls_state.buildkit)),
-            Ok(Err(e)) => {
-                Ok(AppInner::ErrorWindow(ErrorWindowState {
-                    ls_state: self.ls_state,
-                    message: e.to_string(),
-                }))
-            },
-            Err(RecvTimeoutError::Timeout) => Ok(AppInner::Removing(self)),
-            Err(RecvTimeoutError::Disconnected) => {
-                color_eyre::eyre::bail!(
-                    "RemovingState channel cannot be disconnected without returning a result"
-                )
+    /// - [`AppInner::ErrorWindow`] — some items failed to be removed
+    pub fn try_recv(mut self) -> color_eyre::Result<AppInner<Item>> {
+        match self.progress.try_recv()? {
+            Some(items) => {
+                let errors = self.progress.errors(LsItem::key);
+                Ok(self.ls_state.finish(items, errors))
             },
+            None => Ok(AppInner::Removing(self)),
         }
     }
 }
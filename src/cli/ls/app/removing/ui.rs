@@ -1,15 +1,8 @@
-use std::fmt::Display;
-
-use ratatui::{
-    layout::{Constraint, Layout, Rect},
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph, Widget},
-};
+use ratatui::{layout::Rect, style::Color, widgets::Widget};
 
 use crate::{
     buildkit::{container_info::SCellContainerInfo, image_info::SCellImageInfo},
-    cli::ls::app::removing::RemovingState,
+    cli::ls::app::{ls::LsItem, progress::render_progress, removing::RemovingState},
 };
 
 impl Widget for &RemovingState<SCellContainerInfo> {
@@ -21,7 +14,14 @@ impl Widget for &RemovingState<SCellContainerInfo> {
         Self: Sized,
     {
         self.ls_state.render(area, buf);
-        render_removing(&self.for_removal.id, area, buf);
+        render_progress(
+            &self.progress,
+            "Removing",
+            Color::Red,
+            LsItem::key,
+            area,
+            buf,
+        );
     }
 }
 
@@ -34,54 +34,13 @@ impl Widget for &RemovingState<SCellImageInfo> {
         Self: Sized,
     {
         self.ls_state.render(area, buf);
-        render_removing(&self.for_removal.id, area, buf);
+        render_progress(
+            &self.progress,
+            "Removing",
+            Color::Red,
+            LsItem::key,
+            area,
+            buf,
+        );
     }
 }
-
-#[allow(clippy::indexing_slicing)]
-fn render_removing(
-    item: impl Display,
-    area: Rect,
-    buf: &mut ratatui::prelude::Buffer,
-) {
-    let vertical = Layout::vertical([
-        Constraint::Percentage(40),
-        Constraint::Percentage(20),
-        Constraint::Percentage(40),
-    ])
-    .split(area);
-
-    let horizontal = Layout::horizontal([
-        Constraint::Percentage(20),
-        Constraint::Percentage(60),
-        Constraint::Percentage(20),
-    ])
-    .split(vertical[1]);
-
-    let removing_text = vec![
-        Line::from(vec![
-            Span::styled(
-                "Removing",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-            ),
-            Span::styled("...", Style::default().fg(Color::Red)),
-        ]),
-        Line::from(""),
-        Line::from(Span::styled(
-            format!("Removing '{item}'"),
-            Style::default().fg(Color::Gray),
-        )),
-    ];
-
-    Widget::render(Clear, horizontal[1], buf);
-
-    let paragraph = Paragraph::new(removing_text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red)),
-        )
-        .centered();
-
-    paragraph.render(horizontal[1], buf);
-}
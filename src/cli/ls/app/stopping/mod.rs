@@ -1,49 +1,43 @@
 mod ui;
 
-use std::sync::mpsc::{Receiver, RecvTimeoutError};
-
 use crate::{
     buildkit::container_info::SCellContainerInfo,
-    cli::{
-        MIN_FPS,
-        ls::app::{AppInner, AppItemSuperTrait, error_window::ErrorWindowState, ls::LsState},
+    cli::ls::app::{
+        AppInner, AppItemSuperTrait,
+        ls::{LsItem, LsState},
+        progress::Progress,
     },
 };
 
-/// Holds the state while a item is being stopped in the background.
+/// Holds the state while the items are being stopped in the background.
 ///
-/// The spawned task stops the item and then re-fetches the full
-/// items list, sending the result back over the channel.
+/// The spawned task stops the items concurrently and then re-fetches the full
+/// items list, sending the per-item results and the list back over the channel.
 pub struct StoppingState<Item> {
-    pub for_stop: Item,
+    pub progress: Progress<Item>,
     pub ls_state: LsState<Item>,
-    pub rx: Receiver<color_eyre::Result<Vec<Item>>>,
 }
 
 impl StoppingState<SCellContainerInfo> {
-    /// Spawns a background task that stops `container` and re-fetches the list,
-    /// returning a [`StoppingState`] to track progress.
+    /// Spawns a background task that stops `for_stop` containers and re-fetches the
+    /// list, returning a [`StoppingState`] to track progress.
     pub fn stop(
         ls_state: LsState<SCellContainerInfo>,
-        for_stop: SCellContainerInfo,
+        for_stop: Vec<SCellContainerInfo>,
     ) -> AppInner<SCellContainerInfo> {
         let buildkit = ls_state.buildkit.clone();
-        let (tx, rx) = std::sync::mpsc::channel();
-        tokio::spawn({
-            let container = for_stop.clone();
-            async move {
-                let res = buildkit.stop_container(&container).await;
-                let res = match res {
-                    Ok(()) => buildkit.list_containers().await,
-                    Err(e) => Err(e),
-                };
-                drop(tx.send(res));
-            }
-        });
+        let actions = for_stop
+            .iter()
+            .cloned()
+            .map(|container| {
+                let buildkit = buildkit.clone();
+                async move { buildkit.stop_container(&container).await }
+            })
+            .collect();
+        let refresh = async move { buildkit.list_containers().await };
         AppInner::Stopping(Self {
-            for_stop,
+            progress: Progress::spawn(for_stop, actions, refresh),
             ls_state,
-            rx,
         })
     }
 }
@@ -53,21 +47,14 @@ impl<Item: Clone + AppItemSuperTrait> StoppingState<Item> {
     ///
     /// - [`AppInner::Stopping`] — still waiting (self is returned back)
     /// - [`AppInner::Ls`] — stop succeeded; contains the refreshed item list
-    pub fn try_recv(self) -> color_eyre::Result<AppInner<Item>> {
-        match self.rx.recv_timeout(MIN_FPS) {
-            Ok(Ok(items)) => Ok(LsState::ls(items, self.ls_state.buildkit)),
-            Ok(Err(e)) => {
-                Ok(AppInner::ErrorWindow(ErrorWindowState {
-                    ls_state: self.ls_state,
-                    message: e.to_string(),
-                }))
-            },
-            Err(RecvTimeoutError::Timeout) => Ok(AppInner::Stopping(self)),
-            Err(RecvTimeoutError::Disconnected) => {
-                color_eyre::eyre::bail!(
-                    "StoppingState channel cannot be disconnected without returning a result"
-                )
+    /// - [`AppInner::ErrorWindow`] — some items failed to stop
+    pub fn try_recv(mut self) -> color_eyre::Result<AppInner<Item>> {
+        match self.progress.try_recv()? {
+            Some(items) => {
+                let errors = self.progress.errors(LsItem::key);
+                Ok(self.ls_state.finish(items, errors))
             },
+            None => Ok(AppInner::Stopping(self)),
         }
     }
 }
@@ -40,7 +40,10 @@ impl Widget for &mut AppInner<SCellContainerInfo> {
             AppInner::Inspect(state) => {
                 state.render(inner, buf);
             },
-            AppInner::Exit => {},
+            AppInner::Attaching(state) => {
+                state.render(inner, buf);
+            },
+            AppInner::Attached(_) | AppInner::Exit => {},
         }
     }
 }
@@ -75,7 +78,8 @@ impl Widget for &mut AppInner<SCellImageInfo> {
             AppInner::Inspect(state) => {
                 state.render(inner, buf);
             },
-            AppInner::Exit => {},
+            // Images could not be attached to
+            AppInner::Attaching(_) | AppInner::Attached(_) | AppInner::Exit => {},
         }
     }
 }
@@ -0,0 +1,314 @@
+//! Non-interactive `scell ls` output, suitable for scripting.
+
+use std::fmt::Write;
+
+use crate::buildkit::{
+    container_info::{SCellContainerInfo, Status},
+    image_info::SCellImageInfo,
+};
+
+const EMPTY: &str = "<empty>";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LsFormat {
+    /// JSON document with the `containers` and `images` lists.
+    Json,
+    /// YAML document with the `containers` and `images` lists.
+    Yaml,
+    /// Plain text table, one per list.
+    Table,
+}
+
+/// Filters, applied to the listed containers and images.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LsFilter {
+    /// List the images. If neither `images` nor `containers` is set, both are listed.
+    pub images: bool,
+    /// List the containers. If neither `images` nor `containers` is set, both are listed.
+    pub containers: bool,
+    /// List only the orphaned containers and images.
+    pub orphans: bool,
+    /// List only the containers with the status. Images have no status, so they are not
+    /// listed with it.
+    pub status: Option<Status>,
+}
+
+impl LsFilter {
+    fn show_containers(self) -> bool {
+        self.containers || !self.images
+    }
+
+    fn show_images(self) -> bool {
+        self.images || (!self.containers && self.status.is_none())
+    }
+
+    fn container(
+        self,
+        c: &SCellContainerInfo,
+    ) -> bool {
+        (!self.orphans || c.orphan) && self.status.is_none_or(|status| c.status == status)
+    }
+
+    fn image(
+        self,
+        i: &SCellImageInfo,
+    ) -> bool {
+        !self.orphans || i.orphan
+    }
+}
+
+#[derive(serde::Serialize)]
+struct LsOutput {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    containers: Option<Vec<ContainerRecord>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    images: Option<Vec<ImageRecord>>,
+}
+
+#[derive(serde::Serialize)]
+struct ContainerRecord {
+    id: String,
+    service_name: Option<String>,
+    orphan: bool,
+    status: String,
+    target: Option<String>,
+    location: Option<String>,
+    created_at: Option<String>,
+    image_desc: Option<yaml_serde::Value>,
+    container_desc: Option<yaml_serde::Value>,
+}
+
+impl From<SCellContainerInfo> for ContainerRecord {
+    fn from(c: SCellContainerInfo) -> Self {
+        Self {
+            id: c.id.to_string(),
+            service_name: c.service_name.as_ref().map(ToString::to_string),
+            orphan: c.orphan,
+            status: c.status.to_string(),
+            target: c.target.as_ref().map(ToString::to_string),
+            location: c.location.as_ref().map(|l| l.display().to_string()),
+            created_at: c
+                .created_at
+                .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, false)),
+            image_desc: c.image_desc,
+            container_desc: c.container_desc,
+        }
+    }
+}
+
+#[derive(serde::Serialize)]
+struct ImageRecord {
+    id: String,
+    orphan: bool,
+    target: Option<String>,
+    location: Option<String>,
+    created_at: Option<String>,
+    desc: Option<yaml_serde::Value>,
+}
+
+impl From<SCellImageInfo> for ImageRecord {
+    fn from(i: SCellImageInfo) -> Self {
+        Self {
+            id: i.id.to_string(),
+            orphan: i.orphan,
+            target: i.target.as_ref().map(ToString::to_string),
+            location: i.location.as_ref().map(|l| l.display().to_string()),
+            created_at: i
+                .created_at
+                .map(|dt| dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, false)),
+            desc: i.desc,
+        }
+    }
+}
+
+/// Renders the containers and images, matching the filter, in the format.
+pub fn render(
+    containers: Vec<SCellContainerInfo>,
+    images: Vec<SCellImageInfo>,
+    format: LsFormat,
+    filter: LsFilter,
+) -> color_eyre::Result<String> {
+    let output = LsOutput {
+        containers: filter.show_containers().then(|| {
+            containers
+                .into_iter()
+                .filter(|c| filter.container(c))
+                .map(Into::into)
+                .collect()
+        }),
+        images: filter.show_images().then(|| {
+            images
+                .into_iter()
+                .filter(|i| filter.image(i))
+                .map(Into::into)
+                .collect()
+        }),
+    };
+    match format {
+        LsFormat::Json => {
+            let mut out = serde_json::to_string_pretty(&output)?;
+            out.push('\n');
+            Ok(out)
+        },
+        LsFormat::Yaml => Ok(yaml_serde::to_string(&output)?),
+        LsFormat::Table => Ok(render_tables(&output)),
+    }
+}
+
+fn render_tables(output: &LsOutput) -> String {
+    let containers = output.containers.as_ref().map(|containers| {
+        let rows = containers.iter().map(|c| {
+            [
+                c.id.clone(),
+                c.service_name.clone().unwrap_or_else(|| "-".to_string()),
+                c.target.clone().unwrap_or_else(|| EMPTY.to_string()),
+                c.location.clone().unwrap_or_else(|| EMPTY.to_string()),
+                c.created_at.clone().unwrap_or_else(|| EMPTY.to_string()),
+                if c.orphan {
+                    format!("{} (orphan)", c.status)
+                } else {
+                    c.status.clone()
+                },
+            ]
+        });
+        render_table(
+            [
+                "ID",
+                "SERVICE",
+                "TARGET",
+                "BLUEPRINT LOCATION",
+                "CREATED AT",
+                "STATUS",
+            ],
+            rows,
+        )
+    });
+    let images = output.images.as_ref().map(|images| {
+        let rows = images.iter().map(|i| {
+            [
+                i.id.clone(),
+                i.target.clone().unwrap_or_else(|| EMPTY.to_string()),
+                i.location.clone().unwrap_or_else(|| EMPTY.to_string()),
+                i.created_at.clone().unwrap_or_else(|| EMPTY.to_string()),
+                if i.orphan { "orphan" } else { "-" }.to_string(),
+            ]
+        });
+        render_table(
+            ["ID", "TARGET", "BLUEPRINT LOCATION", "CREATED AT", "STATUS"],
+            rows,
+        )
+    });
+    // Tables are separated with an empty line
+    containers
+        .into_iter()
+        .chain(images)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders the rows as a table with left aligned columns, separated by two spaces.
+fn render_table<const N: usize>(
+    header: [&str; N],
+    rows: impl Iterator<Item = [String; N]>,
+) -> String {
+    let rows: Vec<[String; N]> = std::iter::once(header.map(ToString::to_string))
+        .chain(rows)
+        .collect();
+    let widths = rows.iter().fold([0; N], |mut widths, row| {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = cell.chars().count().max(*width);
+        }
+        widths
+    });
+
+    let mut out = String::new();
+    for row in &rows {
+        let mut line = String::new();
+        for (width, cell) in widths.iter().zip(row) {
+            let _ = write!(line, "{cell:<width$}  ");
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::cli::ls::test_utils::{container, image};
+
+    #[test_case(
+        LsFilter::default(),
+        "ID          SERVICE  TARGET  BLUEPRINT LOCATION  CREATED AT  STATUS\n\
+         scell-a     -        main    /blueprint          <empty>     running\n\
+         scell-long  -        main    /blueprint          <empty>     exited (orphan)\n\
+         \n\
+         ID       TARGET   BLUEPRINT LOCATION  CREATED AT  STATUS\n\
+         scell-a  <empty>  <empty>             <empty>     -\n\
+         scell-b  <empty>  <empty>             <empty>     orphan\n"
+        ; "all"
+    )]
+    #[test_case(
+        LsFilter { images: true, orphans: true, ..Default::default() },
+        "ID       TARGET   BLUEPRINT LOCATION  CREATED AT  STATUS\n\
+         scell-b  <empty>  <empty>             <empty>     orphan\n"
+        ; "orphan images"
+    )]
+    #[test_case(
+        LsFilter { containers: true, status: Some(Status::Running), ..Default::default() },
+        "ID       SERVICE  TARGET  BLUEPRINT LOCATION  CREATED AT  STATUS\n\
+         scell-a  -        main    /blueprint          <empty>     running\n"
+        ; "running containers"
+    )]
+    #[test_case(
+        LsFilter { status: Some(Status::Exited), ..Default::default() },
+        "ID          SERVICE  TARGET  BLUEPRINT LOCATION  CREATED AT  STATUS\n\
+         scell-long  -        main    /blueprint          <empty>     exited (orphan)\n"
+        ; "exited containers"
+    )]
+    fn render_table_test(
+        filter: LsFilter,
+        expected: &str,
+    ) {
+        let containers = vec![container("scell-a", Status::Running), SCellContainerInfo {
+            orphan: true,
+            ..container("scell-long", Status::Exited)
+        }];
+        let images = vec![image("scell-a", false), image("scell-b", true)];
+        assert_eq!(
+            render(containers, images, LsFormat::Table, filter).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn render_json_test() {
+        let containers = vec![container("scell-a", Status::Running)];
+        let filter = LsFilter {
+            containers: true,
+            ..Default::default()
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&render(containers, Vec::new(), LsFormat::Json, filter).unwrap())
+                .unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "containers": [{
+                    "id": "scell-a",
+                    "service_name": null,
+                    "orphan": false,
+                    "status": "running",
+                    "target": "main",
+                    "location": "/blueprint",
+                    "created_at": null,
+                    "image_desc": null,
+                    "container_desc": null,
+                }]
+            })
+        );
+    }
+}
@@ -1,14 +1,28 @@
 mod app;
+mod format;
+#[cfg(test)]
+mod test_utils;
+
+pub use format::{LsFilter, LsFormat};
 
 use crate::{
     buildkit::BuildKitD,
     cli::{ls::app::App, terminal::Terminal},
 };
 
-pub async fn ls() -> color_eyre::Result<()> {
+pub async fn ls(
+    format: Option<LsFormat>,
+    filter: LsFilter,
+) -> color_eyre::Result<()> {
     let buildkit = BuildKitD::start().await?;
+    if let Some(format) = format {
+        let containers = buildkit.list_containers().await?;
+        let images = buildkit.list_images().await?;
+        print!("{}", format::render(containers, images, format, filter)?);
+        return Ok(());
+    }
     let mut terminal = Terminal::new()?;
-    let res = App::run(&buildkit, &mut terminal);
+    let res = App::run(&buildkit, &mut terminal).await;
     ratatui::try_restore()?;
     res
 }
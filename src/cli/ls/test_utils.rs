@@ -0,0 +1,44 @@
+//! Builders of the listed items, shared by the `ls` tests.
+
+use crate::buildkit::{
+    container_info::{SCellContainerInfo, Status},
+    image_info::SCellImageInfo,
+};
+
+/// A `main` target container from the `/blueprint` location, the rest of the fields could
+/// be overridden with the struct update syntax.
+pub fn container(
+    id: &str,
+    status: Status,
+) -> SCellContainerInfo {
+    SCellContainerInfo {
+        id: id.parse().unwrap(),
+        service_name: None,
+        orphan: false,
+        status,
+        image_id: None,
+        location: Some("/blueprint".into()),
+        target: Some("main".parse().unwrap()),
+        image_desc: None,
+        container_desc: None,
+        created_at: None,
+        ports: Vec::new(),
+        shell: None,
+        docker_image_id: String::new(),
+    }
+}
+
+pub fn image(
+    id: &str,
+    orphan: bool,
+) -> SCellImageInfo {
+    SCellImageInfo {
+        id: id.parse().unwrap(),
+        orphan,
+        location: None,
+        target: None,
+        desc: None,
+        created_at: None,
+        docker_image_id: String::new(),
+    }
+}
@@ -1,19 +1,32 @@
 //! Command Line Interface implementation
 
+mod check;
 mod cleanup;
+mod export;
+mod graph;
 mod help_window_widget;
 mod init;
 mod ls;
+mod port;
+mod render;
+mod replay;
 mod run;
 mod stop;
 mod terminal;
 
-use std::{path::PathBuf, time::Duration};
+use std::{path::PathBuf, process::ExitCode, time::Duration};
 
 use clap::{Parser, Subcommand};
 use color_eyre::Section;
 
-use crate::{crate_info, error::UserError, scell::types::name::TargetName};
+use crate::{
+    buildkit::container_info::Status,
+    cli::{graph::GraphFormat, ls::LsFormat},
+    crate_info,
+    error::UserError,
+    pty::SessionLogFormat,
+    scell::types::name::TargetName,
+};
 
 // 60 frames per second
 const MIN_FPS: Duration = Duration::from_millis(1000 / 60);
@@ -43,6 +56,25 @@ pub struct Cli {
     #[clap(short, long)]
     global: bool,
 
+    /// Record the session into the asciicast v2 file, which could be played back with
+    /// `scell replay` or any asciicast player (e.g. `asciinema play`)
+    #[clap(long, value_name = "FILE")]
+    record: Option<PathBuf>,
+
+    /// Dump the whole session scrollback into the file and keep appending the session
+    /// output to it, while the session runs
+    #[clap(long, value_name = "FILE")]
+    log_session: Option<PathBuf>,
+
+    /// Format of the session log
+    #[clap(
+        long,
+        value_name = "FORMAT",
+        default_value = "plain",
+        requires = "log_session"
+    )]
+    log_format: SessionLogFormat,
+
     #[clap(subcommand)]
     command: Option<Commands>,
 }
@@ -60,7 +92,122 @@ pub enum Commands {
         global: bool,
     },
     /// List all existing Shell-Cell containers
-    Ls,
+    Ls {
+        /// Print the containers and images in the format, instead of launching the
+        /// interactive UI
+        #[clap(long, value_name = "FORMAT")]
+        format: Option<LsFormat>,
+
+        /// List only the images
+        #[clap(long, requires = "format")]
+        images: bool,
+
+        /// List only the containers
+        #[clap(long, requires = "format")]
+        containers: bool,
+
+        /// List only the orphaned containers and images
+        #[clap(long, requires = "format")]
+        orphans: bool,
+
+        /// List only the containers with the status
+        #[clap(
+            long,
+            value_name = "STATUS",
+            requires = "format",
+            conflicts_with = "images"
+        )]
+        status: Option<Status>,
+    },
+    /// Show the host ports published for the running Shell-Cell container, including the
+    /// ones randomly picked by Docker
+    Port {
+        /// Target name to show the ports for, instead of 'main' (optional)
+        #[clap(value_name = "TARGET")]
+        target: Option<TargetName>,
+
+        /// Path to the directory with 'scell.cue' file
+        #[clap(short, long, value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+
+        /// Use the global blueprint located in the Shell-Cell home directory (`~/.scell`)
+        #[clap(short, long)]
+        global: bool,
+    },
+    /// Compile the blueprint without Docker, reporting all the errors found, e.g. to use
+    /// in the pre-commit hooks
+    Check {
+        /// Target name to check, instead of 'main' (optional)
+        #[clap(short, long, conflicts_with = "all_targets")]
+        target: Option<TargetName>,
+
+        /// Path to the directory with 'scell.cue' file
+        #[clap(short, long, value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+
+        /// Check every target of the blueprint, not only the entry point
+        #[clap(long)]
+        all_targets: bool,
+    },
+    /// Show how the target is resolved: its `from` chain across the blueprint files, its
+    /// services and the targets, the effective `shell`, `hang` and `config` come from
+    Graph {
+        /// Target name to show the graph for, instead of 'main' (optional)
+        #[clap(short, long)]
+        target: Option<TargetName>,
+
+        /// Path to the directory with 'scell.cue' file
+        #[clap(short, long, value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+
+        /// Output format
+        #[clap(long, value_name = "FORMAT", default_value = "tree")]
+        format: GraphFormat,
+    },
+    /// Show the Dockerfile and the build context, the target's image is built from
+    Render {
+        /// Target name to render, instead of 'main' (optional)
+        #[clap(short, long)]
+        target: Option<TargetName>,
+
+        /// Path to the directory with 'scell.cue' file
+        #[clap(short, long, value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+
+        /// Print only the rendered Dockerfile
+        #[clap(long, conflicts_with_all = ["context_list", "tar"])]
+        dockerfile: bool,
+
+        /// Print only the list of files in the build context, with their sizes
+        #[clap(long, conflicts_with = "tar")]
+        context_list: bool,
+
+        /// Export the build context, including the Dockerfile, as a tar archive
+        #[clap(long, value_name = "FILE")]
+        tar: Option<PathBuf>,
+    },
+    /// Export the target as a standalone Docker Compose project: the rendered Dockerfiles
+    /// with their build contexts and a `compose.yaml`, describing the main container and
+    /// its services
+    Export {
+        /// Directory to export the project into, must be empty or not exist
+        #[clap(value_name = "DIR")]
+        dir: PathBuf,
+
+        /// Target name to export, instead of 'main' (optional)
+        #[clap(short, long)]
+        target: Option<TargetName>,
+
+        /// Path to the directory with 'scell.cue' file
+        #[clap(short, long, value_name = "PATH", default_value = ".")]
+        path: PathBuf,
+    },
+    /// Play back the session, recorded with `scell --record`
+    Replay {
+        /// Path to the asciicast v2 file
+        #[clap(value_name = "FILE")]
+        file: PathBuf,
+    },
     /// Stop all running Shell-Cell containers
     Stop {
         /// Run silently without any output
@@ -78,7 +225,8 @@ pub enum Commands {
 }
 
 impl Cli {
-    pub async fn exec(self) -> color_eyre::Result<()> {
+    /// Executes the command, returning the exit code `scell` should exit with.
+    pub async fn exec(self) -> color_eyre::Result<ExitCode> {
         const SUGGESTION: &str = "If you've got a second, please toss a full backtrace into your ticket—it helps us squash the bug way faster! You can grab it by running the app with `RUST_BACKTRACE=1`.";
 
         self.exec_inner().await.map_err(|e| {
@@ -91,28 +239,74 @@ impl Cli {
                 ))
                 .suggestion(SUGGESTION)
             }
-        })?;
-
-        Ok(())
+        })
     }
 
-    pub async fn exec_inner(self) -> color_eyre::Result<()> {
+    pub async fn exec_inner(self) -> color_eyre::Result<ExitCode> {
         match self.command {
             None => {
-                run::run(
+                return run::run(
                     self.scell_path,
                     self.target,
                     self.detach,
                     self.quiet,
                     self.global,
+                    run::SessionOutput {
+                        record: self.record,
+                        log: self.log_session.map(|log| (log, self.log_format)),
+                    },
                 )
-                .await?;
+                .await;
             },
             Some(Commands::Init { path, global }) => init::init(path, global)?,
-            Some(Commands::Ls) => ls::ls().await?,
+            Some(Commands::Ls {
+                format,
+                images,
+                containers,
+                orphans,
+                status,
+            }) => {
+                ls::ls(format, ls::LsFilter {
+                    images,
+                    containers,
+                    orphans,
+                    status,
+                })
+                .await?;
+            },
+            Some(Commands::Port {
+                target,
+                path,
+                global,
+            }) => port::port(path, target, global).await?,
+            Some(Commands::Check {
+                path,
+                target,
+                all_targets,
+            }) => check::check(path, target, all_targets)?,
+            Some(Commands::Graph {
+                target,
+                path,
+                format,
+            }) => graph::graph(path, target, format)?,
+            Some(Commands::Render {
+                target,
+                path,
+                dockerfile,
+                context_list,
+                tar,
+            }) => {
+                render::render(path, target, render::RenderOutput {
+                    dockerfile,
+                    context_list,
+                    tar,
+                })?;
+            },
+            Some(Commands::Export { dir, target, path }) => export::export(path, target, &dir)?,
+            Some(Commands::Replay { file }) => replay::replay(&file)?,
             Some(Commands::Stop { silent }) => stop::stop(silent).await?,
             Some(Commands::Cleanup { all }) => cleanup::cleanup(all).await?,
         }
-        Ok(())
+        Ok(ExitCode::SUCCESS)
     }
 }
@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+use crate::{
+    buildkit::BuildKitD,
+    scell::{SCell, types::name::TargetName},
+    scell_home_dir,
+};
+
+pub async fn port(
+    scell_path: PathBuf,
+    target: Option<TargetName>,
+    global: bool,
+) -> color_eyre::Result<()> {
+    let scell_path = if global {
+        scell_home_dir()?
+    } else {
+        scell_path
+    };
+    let scell = SCell::compile(scell_path, target)?;
+    let buildkit = BuildKitD::start().await?;
+    for port in buildkit.container_ports(&scell).await? {
+        println!("{port}");
+    }
+    Ok(())
+}
@@ -0,0 +1,94 @@
+//! `scell render` shows the Dockerfile and the build context, the image is built from.
+
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::Context;
+use indicatif::HumanBytes;
+
+use crate::scell::{SCell, image::SCellImage, types::name::TargetName};
+
+/// What to render, everything (the Dockerfile and the context files) if nothing is set.
+pub struct RenderOutput {
+    pub dockerfile: bool,
+    pub context_list: bool,
+    pub tar: Option<PathBuf>,
+}
+
+pub fn render(
+    scell_path: PathBuf,
+    target: Option<TargetName>,
+    output: RenderOutput,
+) -> color_eyre::Result<()> {
+    let scell = SCell::compile(scell_path, target)?;
+    let image = scell.image();
+
+    if let Some(tar) = output.tar {
+        return write_tar(image, &tar);
+    }
+    let all = !output.dockerfile && !output.context_list;
+    if output.dockerfile || all {
+        print!("{}", image.dump_to_string()?);
+    }
+    if all {
+        println!();
+    }
+    if output.context_list || all {
+        print!("{}", context_list(&image.context_files()?)?);
+    }
+    Ok(())
+}
+
+fn write_tar(
+    image: &SCellImage,
+    path: &Path,
+) -> color_eyre::Result<()> {
+    let (tar, _) = image.image_tar_artifact_bytes()?;
+    std::fs::write(path, tar).context(format!(
+        "Cannot write the build context to '{}'",
+        path.display()
+    ))?;
+    println!("Build context is written to '{}'", path.display());
+    Ok(())
+}
+
+/// Returns the context files, one per line with its size, and their total size.
+fn context_list(files: &[(PathBuf, u64)]) -> color_eyre::Result<String> {
+    let mut res = String::new();
+    let mut total: u64 = 0;
+    for (path, size) in files {
+        total = total.saturating_add(*size);
+        writeln!(
+            &mut res,
+            "{:>10}  {}",
+            HumanBytes(*size).to_string(),
+            path.display()
+        )?;
+    }
+    writeln!(
+        &mut res,
+        "{:>10}  total, {} files",
+        HumanBytes(total).to_string(),
+        files.len()
+    )?;
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn context_list_test() {
+        let files = vec![
+            (PathBuf::from("home/user/project/file.txt"), 2048),
+            (PathBuf::from("Dockerfile"), 100),
+        ];
+        assert_eq!(
+            context_list(&files).unwrap(),
+            "  2.00 KiB  home/user/project/file.txt\n     100 B  Dockerfile\n  2.10 KiB  total, 2 files\n"
+        );
+    }
+}
@@ -0,0 +1,50 @@
+mod ui;
+
+use std::path::{Path, PathBuf};
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+
+use crate::{
+    cli::{MIN_FPS, terminal::Terminal},
+    pty::Replay,
+};
+
+/// Plays back a recorded session, until the user exits with `q`, `Esc`, `Ctrl-C` or
+/// `Ctrl-D`.
+pub struct App {
+    replay: Replay,
+    path: PathBuf,
+}
+
+impl App {
+    pub fn run(
+        replay: Replay,
+        path: &Path,
+        terminal: &mut Terminal,
+    ) -> color_eyre::Result<()> {
+        let mut app = Self {
+            replay,
+            path: path.to_path_buf(),
+        };
+        loop {
+            app.replay.update();
+
+            terminal.draw(|f| {
+                f.render_widget(&app, f.area());
+            })?;
+
+            if event::poll(MIN_FPS)?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char('c' | 'd') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(());
+                    },
+                    _ => {},
+                }
+            }
+        }
+    }
+}
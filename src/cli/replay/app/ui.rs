@@ -0,0 +1,41 @@
+use ratatui::{
+    style::Style,
+    widgets::{Block, Borders, Widget},
+};
+
+use crate::cli::replay::app::App;
+
+impl Widget for &App {
+    fn render(
+        self,
+        area: ratatui::prelude::Rect,
+        buf: &mut ratatui::prelude::Buffer,
+    ) where
+        Self: Sized,
+    {
+        let status = if self.replay.is_finished() {
+            "Finished"
+        } else {
+            "Playing"
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::new().light_magenta())
+            .title(format!(
+                "Replay | {}{} | {status} {:.1}s / {:.1}s",
+                self.path.display(),
+                self.replay
+                    .title()
+                    .map(|title| format!(" | {title}"))
+                    .unwrap_or_default(),
+                self.replay.elapsed().as_secs_f64(),
+                self.replay.duration().as_secs_f64(),
+            ))
+            .title_bottom("q / Esc: exit");
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        // The screen keeps the recorded size, so it is not resized to the current area
+        tui_term::widget::PseudoTerminal::new(self.replay.screen()).render(inner, buf);
+    }
+}
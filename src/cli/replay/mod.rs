@@ -0,0 +1,20 @@
+mod app;
+
+use std::path::Path;
+
+use crate::{
+    cli::{replay::app::App, terminal::Terminal},
+    error::WrapUserError,
+    pty::Replay,
+};
+
+pub fn replay(path: &Path) -> color_eyre::Result<()> {
+    let replay = Replay::open(path).wrap_user_err(format!(
+        "Cannot read the asciicast recording '{}'",
+        path.display()
+    ))?;
+    let mut terminal = Terminal::new()?;
+    let res = App::run(replay, path, &mut terminal);
+    ratatui::try_restore()?;
+    res
+}
@@ -12,7 +12,7 @@ impl HelpWindowState {
         event: &Event,
     ) -> App {
         if let Event::Key(key) = event
-            && key.kind == KeyEventKind::Press
+            && key.kind != KeyEventKind::Release
         {
             match key.code {
                 KeyCode::Char('h') if key.modifiers.contains(KeyModifiers::CONTROL) => {
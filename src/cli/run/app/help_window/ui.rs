@@ -27,18 +27,70 @@ const HELP_ENTRIES: &[HelpEntry] = &[
         key_color: Color::Yellow,
         description: "Scroll the screen",
     },
+    HelpEntry::Shortcut {
+        key: "g / G",
+        key_color: Color::Yellow,
+        description: "Jump to the top / bottom of the scrollback",
+    },
+    HelpEntry::Shortcut {
+        key: "/ / ?",
+        key_color: Color::Yellow,
+        description: "Search the scrollback forward / backward (regex)",
+    },
+    HelpEntry::Shortcut {
+        key: "n / N",
+        key_color: Color::Yellow,
+        description: "Jump to the next / previous match",
+    },
+    HelpEntry::Shortcut {
+        key: "[",
+        key_color: Color::Yellow,
+        description: "Enter copy mode",
+    },
+    HelpEntry::Shortcut {
+        key: "e",
+        key_color: Color::Yellow,
+        description: "Export the scrollback and keep logging into ~/.scell/logs",
+    },
     HelpEntry::Shortcut {
         key: "Esc / Ctrl-B",
         key_color: Color::Yellow,
         description: "Exit command mode",
     },
     HelpEntry::Blank,
+    HelpEntry::Section("Copy mode"),
+    HelpEntry::Shortcut {
+        key: "h / j / k / l",
+        key_color: Color::Yellow,
+        description: "Move the cursor (also arrows, 0 / $, g / G)",
+    },
+    HelpEntry::Shortcut {
+        key: "v / V",
+        key_color: Color::Yellow,
+        description: "Start a character / line selection",
+    },
+    HelpEntry::Shortcut {
+        key: "y / Enter",
+        key_color: Color::Yellow,
+        description: "Yank the selection into the host clipboard",
+    },
+    HelpEntry::Shortcut {
+        key: "q / Esc",
+        key_color: Color::Yellow,
+        description: "Exit copy mode",
+    },
+    HelpEntry::Blank,
     HelpEntry::Section("General"),
     HelpEntry::Shortcut {
         key: "Ctrl-H",
         key_color: Color::Yellow,
         description: "Open / close this help window",
     },
+    HelpEntry::Shortcut {
+        key: "Mouse wheel",
+        key_color: Color::Yellow,
+        description: "Scroll the screen, unless the running app captures the mouse",
+    },
 ];
 
 impl Widget for &mut HelpWindowState {
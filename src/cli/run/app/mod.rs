@@ -5,17 +5,23 @@ mod ui;
 
 use std::path::Path;
 
+use color_eyre::eyre::ContextCompat;
 use ratatui::crossterm::event::{self, Event, KeyEventKind};
 
 use crate::{
-    buildkit::BuildKitD,
+    buildkit::{BuildKitD, container_info::SCellContainerInfo},
     cli::{
         MIN_FPS,
-        run::app::{
-            help_window::HelpWindowState, preparing::PreparingState, running_pty::RunningPtyState,
+        run::{
+            SessionOutput,
+            app::{
+                help_window::HelpWindowState, preparing::PreparingState,
+                running_pty::RunningPtyState,
+            },
         },
         terminal::Terminal,
     },
+    pty::Pty,
     scell::types::name::TargetName,
 };
 
@@ -23,7 +29,9 @@ pub enum App {
     Preparing(PreparingState),
     RunningPty(Box<RunningPtyState>),
     HelpWindow(HelpWindowState),
-    Finished,
+    /// The session is finished, with the exit code of the shell if it exited by itself
+    /// (`None` when detached from).
+    Finished(Option<i64>),
     Exit,
 }
 
@@ -34,63 +42,128 @@ impl App {
         entry_target: Option<TargetName>,
         detach: bool,
         quiet: bool,
+        output: SessionOutput,
         terminal: &mut Terminal,
-    ) -> color_eyre::Result<()>
+    ) -> color_eyre::Result<Option<i64>>
     where
         P: AsRef<Path> + Send + 'static,
     {
         // First step
-        let mut app =
-            PreparingState::prepare(buildkit.clone(), scell_path, entry_target, detach, quiet);
+        let app = PreparingState::prepare(
+            buildkit.clone(),
+            scell_path,
+            entry_target,
+            detach,
+            quiet,
+            output,
+        );
+        Self::event_loop(app, buildkit, terminal).await
+    }
 
+    /// Runs the session for the already attached `pty`, until it is finished.
+    pub async fn attach(
+        buildkit: &BuildKitD,
+        pty: Pty,
+        container: &SCellContainerInfo,
+        terminal: &mut Terminal,
+    ) -> color_eyre::Result<Option<i64>> {
+        let state = RunningPtyState::new(
+            pty,
+            container.id.clone(),
+            container
+                .target
+                .clone()
+                .context("'Shell-Cell' container must have a target")?,
+            container
+                .location
+                .clone()
+                .context("'Shell-Cell' container must have a blueprint location")?,
+        );
+        Self::event_loop(App::RunningPty(state.into()), buildkit, terminal).await
+    }
+
+    /// Runs the event loop until the app exits, returning the exit code of the shell, if
+    /// it exited by itself.
+    async fn event_loop(
+        mut app: App,
+        buildkit: &BuildKitD,
+        terminal: &mut Terminal,
+    ) -> color_eyre::Result<Option<i64>> {
+        let mut redraw = true;
+        let mut exit_code = None;
         loop {
             if let App::Preparing(state) = app {
                 app = state.try_update()?;
+                redraw = true;
             }
 
             if let App::RunningPty(ref mut state)
             | App::HelpWindow(HelpWindowState(ref mut state)) = app
             {
                 state.notify_screen_resize(buildkit).await?;
-                state.try_update();
+                redraw |= state.try_update().await;
+            }
+
+            // The shell inside the container exited
+            if let App::RunningPty(ref state) | App::HelpWindow(HelpWindowState(ref state)) = app
+                && state.pty.is_exited()
+            {
+                // The exit code is only informational, so a Docker error does not abort the
+                // session
+                exit_code = buildkit
+                    .shell_exit_code(state.pty.container_session_id())
+                    .await
+                    .unwrap_or_else(|e| {
+                        drop(crate::debugger::Debugger::log_debug(format!(
+                            "Failed to get the shell exit code: {e}"
+                        )));
+                        None
+                    });
+                app = App::Finished(exit_code);
+                redraw = true;
             }
 
             if matches!(app, App::Exit) {
-                return Ok(());
+                return Ok(exit_code);
             }
 
-            terminal.draw(|f| {
-                f.render_widget(&mut app, f.area());
-            })?;
+            // The PTY screen is redrawn only if there was a new output or an input event
+            if redraw {
+                terminal.draw(|f| {
+                    f.render_widget(&mut app, f.area());
+                })?;
+            }
 
-            app = app.handle_key_event()?;
+            (app, redraw) = app.handle_key_event()?;
         }
     }
 
-    fn handle_key_event(mut self) -> color_eyre::Result<Self> {
-        if event::poll(MIN_FPS)? {
-            let event = event::read()?;
-            match self {
-                Self::RunningPty(state) => {
-                    self = state.handle_key_event(&event)?;
-                },
-                Self::HelpWindow(state) => {
-                    self = state.handle_key_event(&event);
-                },
-                Self::Preparing(state) => {
-                    self = state.handle_key_event(&event);
-                },
-                Self::Finished => {
-                    if let Event::Key(key) = event
-                        && key.kind == KeyEventKind::Press
-                    {
-                        // Exit on any key if finished
-                        self = App::Exit;
-                    }
-                },
-                Self::Exit => {},
-            }
+    /// Handles the next input event, if any. Returns `true` if an event was handled.
+    fn handle_key_event(mut self) -> color_eyre::Result<(Self, bool)> {
+        if !event::poll(MIN_FPS)? {
+            return Ok((self, false));
+        }
+        let event = event::read()?;
+        match self {
+            Self::RunningPty(state) => {
+                self = state.handle_key_event(&event)?;
+            },
+            Self::HelpWindow(state) => {
+                self = state.handle_key_event(&event);
+            },
+            Self::Preparing(state) => {
+                self = state.handle_key_event(&event);
+            },
+            Self::Finished(_) => {
+                if let Event::Key(key) = event
+                    && key.kind == KeyEventKind::Press
+                {
+                    // Exit on any key if finished
+                    self = App::Exit;
+                }
+            },
+            Self::Exit => {},
         }
-        Ok(self)
+        Ok((self, true))
     }
 }
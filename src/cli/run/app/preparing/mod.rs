@@ -14,9 +14,12 @@ use crate::{
     buildkit::BuildKitD,
     cli::{
         MIN_FPS,
-        run::app::{App, running_pty::RunningPtyState},
+        run::{
+            SessionOutput,
+            app::{App, running_pty::RunningPtyState},
+        },
     },
-    error::UserError,
+    error::{UserError, WrapUserError},
     pty::Pty,
     scell::{SCell, types::name::TargetName},
 };
@@ -46,6 +49,7 @@ impl PreparingState {
         entry: Option<TargetName>,
         detach: bool,
         quiet: bool,
+        output: SessionOutput,
     ) -> App {
         let (tx, rx) = std::sync::mpsc::channel();
         let (logs_tx, logs_rx) = std::sync::mpsc::channel();
@@ -134,11 +138,35 @@ impl PreparingState {
                 )));
                 buildkit.start_container(&scell).await?;
 
+                for port in buildkit.container_ports(&scell).await? {
+                    drop(logs_tx.send((format!("🔌 Published port {port}"), LogType::MainInfo)));
+                }
+
                 if detach {
                     return color_eyre::eyre::Ok(None);
                 }
 
-                let pty = buildkit.attach_to_shell(&scell).await?;
+                let mut pty = buildkit.attach_to_shell(&scell).await?;
+                if let Some(record) = output.record {
+                    pty.record(&record).wrap_user_err(format!(
+                        "Cannot create the session recording file '{}'",
+                        record.display()
+                    ))?;
+                    drop(logs_tx.send((
+                        format!("⏺️ Recording the session into '{}'", record.display()),
+                        LogType::MainInfo,
+                    )));
+                }
+                if let Some((log, format)) = output.log {
+                    pty.log_session(&log, format).wrap_user_err(format!(
+                        "Cannot open the session log file '{}'",
+                        log.display()
+                    ))?;
+                    drop(logs_tx.send((
+                        format!("📜 Logging the session into '{}'", log.display()),
+                        LogType::MainInfo,
+                    )));
+                }
 
                 drop(logs_tx.send((
                     "🚀 Starting 'Shell-Cell' session".to_string(),
@@ -1,7 +1,9 @@
 use std::path::PathBuf;
 
-use ratatui::crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
-use terminput::Encoding;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind},
+    layout::{Position, Rect},
+};
 use terminput_crossterm::to_terminput;
 
 use crate::{
@@ -10,8 +12,9 @@ use crate::{
         MIN_FPS,
         run::app::{App, help_window::HelpWindowState},
     },
-    pty::Pty,
+    pty::{Clipboard, CopyMotion, Pty, SelectionKind, SessionLogFormat},
     scell::{SCell, name::SCellId, types::name::TargetName},
+    scell_home_dir,
 };
 
 mod ui;
@@ -25,6 +28,12 @@ pub enum InputMode {
     /// Entered with `Ctrl-B`; keys drive the session (scroll, detach) instead of
     /// being sent to the shell. Exited with `Esc`.
     Command,
+    /// Entered from command mode with `/` (forward) or `?` (backward); keys edit the
+    /// scrollback search pattern. `Enter` performs the search, `Esc` cancels it.
+    Search { backward: bool },
+    /// Entered from command mode with `[`; keys move the cursor over the scrollback and
+    /// select text to yank into the host clipboard. Exited with `Esc` or `q`.
+    Copy,
 }
 
 pub struct RunningPtyState {
@@ -35,6 +44,13 @@ pub struct RunningPtyState {
     pub prev_height: u16,
     pub prev_width: u16,
     pub mode: InputMode,
+    /// Area of the outer terminal, occupied by the PTY screen during the last render.
+    pub pty_area: Rect,
+    /// Scrollback search pattern, being edited in the search mode.
+    pub search_pattern: String,
+    /// Result of the last command (e.g. scrollback search or yank), shown in command
+    /// mode.
+    pub status: Option<String>,
 }
 
 impl RunningPtyState {
@@ -43,19 +59,36 @@ impl RunningPtyState {
         scell: &SCell,
     ) -> color_eyre::Result<App> {
         Ok(App::RunningPty(
-            Self {
+            Self::new(
                 pty,
-                container_id: scell.container_id()?,
-                target_name: scell.image().entry_point().clone(),
-                location: scell.image().location().to_path_buf(),
-                prev_height: 0,
-                prev_width: 0,
-                mode: InputMode::Normal,
-            }
+                scell.container_id()?,
+                scell.image().entry_point().clone(),
+                scell.image().location().to_path_buf(),
+            )
             .into(),
         ))
     }
 
+    pub fn new(
+        pty: Pty,
+        container_id: SCellId,
+        target_name: TargetName,
+        location: PathBuf,
+    ) -> Self {
+        Self {
+            pty,
+            container_id,
+            target_name,
+            location,
+            prev_height: 0,
+            prev_width: 0,
+            mode: InputMode::Normal,
+            pty_area: Rect::default(),
+            search_pattern: String::new(),
+            status: None,
+        }
+    }
+
     pub fn scroll_up(
         &mut self,
         lines: usize,
@@ -70,8 +103,9 @@ impl RunningPtyState {
         self.pty.scroll_down(lines);
     }
 
-    pub fn try_update(&mut self) {
-        self.pty.process_stdout_and_stderr(MIN_FPS);
+    /// Processes the pending PTY output. Returns `true` if the screen could have changed.
+    pub async fn try_update(&mut self) -> bool {
+        self.pty.process_stdout_and_stderr(MIN_FPS).await
     }
 
     /// Notify container's session about screen resize
@@ -96,12 +130,53 @@ impl RunningPtyState {
         self: Box<Self>,
         event: &Event,
     ) -> color_eyre::Result<App> {
+        // Focus changes are reported to the shell regardless of the input mode
+        if let Event::FocusGained | Event::FocusLost = event {
+            self.pty.process_focus(matches!(event, Event::FocusGained));
+            return Ok(App::RunningPty(self));
+        }
         match self.mode {
             InputMode::Normal => self.handle_normal_key_event(event),
-            InputMode::Command => Ok(self.handle_command_key_event(event)),
+            InputMode::Command => self.handle_command_key_event(event),
+            InputMode::Search { backward } => Ok(self.handle_search_key_event(event, backward)),
+            InputMode::Copy => self.handle_copy_key_event(event),
         }
     }
 
+    /// Handles mouse events: reports them to the application inside the container if it
+    /// enabled mouse tracking, otherwise the wheel scrolls the scrollback.
+    fn handle_mouse_event(
+        &mut self,
+        mouse: MouseEvent,
+    ) -> color_eyre::Result<()> {
+        const WHEEL_SCROLL_STEP: usize = 3;
+
+        if self.mode == InputMode::Normal
+            && self
+                .pty_area
+                .contains(Position::new(mouse.column, mouse.row))
+        {
+            // Translate the position to be relative to the PTY screen
+            let mouse = MouseEvent {
+                column: mouse.column.saturating_sub(self.pty_area.x),
+                row: mouse.row.saturating_sub(self.pty_area.y),
+                ..mouse
+            };
+            if let terminput::Event::Mouse(mouse) = to_terminput(Event::Mouse(mouse))?
+                && self.pty.process_mouse_event(mouse)
+            {
+                return Ok(());
+            }
+        }
+
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.scroll_up(WHEEL_SCROLL_STEP),
+            MouseEventKind::ScrollDown => self.scroll_down(WHEEL_SCROLL_STEP),
+            _ => {},
+        }
+        Ok(())
+    }
+
     /// Handles keys while forwarding input to the shell. `Ctrl-B` switches to
     /// command mode and `Ctrl-H` opens the help window; everything else is sent to
     /// the container's shell.
@@ -110,26 +185,29 @@ impl RunningPtyState {
         event: &Event,
     ) -> color_eyre::Result<App> {
         if let Event::Paste(to_paste) = event {
-            self.pty.process_stdin(to_paste.as_bytes());
-        } else if let Event::Key(key) = event
-            && key.kind == KeyEventKind::Press
-        {
+            self.pty.scroll_to_bottom();
+            self.pty.process_paste(to_paste);
+        } else if let Event::Mouse(mouse) = event {
+            self.handle_mouse_event(*mouse)?;
+        } else if let Event::Key(key) = event {
             match key.code {
                 KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    self.mode = InputMode::Command;
+                    if key.kind != KeyEventKind::Release {
+                        self.mode = InputMode::Command;
+                    }
                 },
                 KeyCode::Char('h') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    return Ok(App::HelpWindow(HelpWindowState(self)));
+                    if key.kind != KeyEventKind::Release {
+                        return Ok(App::HelpWindow(HelpWindowState(self)));
+                    }
                 },
                 _ => {
-                    let event = to_terminput(Event::Key(*key))?;
-                    // Convert crossterm event to terminput and encode as stdin bytes
-                    let mut buf = [0u8; 32];
-                    if let Ok(written) = event.encode(&mut buf, Encoding::Xterm)
-                        && let Some(bytes) = buf.get(..written)
+                    // Convert crossterm event to terminput and encode as stdin bytes,
+                    // releases are forwarded only if the shell requested them
+                    if let terminput::Event::Key(key) = to_terminput(Event::Key(*key))?
+                        && self.pty.process_key_event(key)
                     {
                         self.pty.scroll_to_bottom();
-                        self.pty.process_stdin(bytes);
                     }
                 },
             }
@@ -139,32 +217,189 @@ impl RunningPtyState {
     }
 
     /// Handles keys while in the `tmux`-style command mode: `d` detaches, the arrow
-    /// and `k`/`j` keys scroll, and `Esc` (or any unrecognized key) returns to normal
-    /// mode without sending anything to the shell.
+    /// and `k`/`j` keys and the mouse wheel scroll, `g`/`G` jump to the top/bottom,
+    /// `/`/`?` start a scrollback search and `n`/`N` jump between its matches, `[`
+    /// enters the copy mode, `e` exports the scrollback into the session log.
+    /// `Esc` (or `Ctrl-B`) returns to normal mode without sending anything to the shell.
     fn handle_command_key_event(
         mut self: Box<Self>,
         event: &Event,
-    ) -> App {
+    ) -> color_eyre::Result<App> {
         const PAGE_SCROLL_STEP: usize = 3;
         const SCROLL_STEP: usize = 1;
 
-        if let Event::Key(key) = event
-            && key.kind == KeyEventKind::Press
+        if let Event::Mouse(mouse) = event {
+            self.handle_mouse_event(*mouse)?;
+        } else if let Event::Key(key) = event
+            && key.kind != KeyEventKind::Release
         {
             match key.code {
-                KeyCode::Char('d') => return App::Finished,
+                KeyCode::Char('d') => return Ok(App::Finished(None)),
                 KeyCode::Up | KeyCode::Char('k') => self.scroll_up(SCROLL_STEP),
                 KeyCode::Down | KeyCode::Char('j') => self.scroll_down(SCROLL_STEP),
                 KeyCode::PageUp => self.scroll_up(PAGE_SCROLL_STEP),
                 KeyCode::PageDown => self.scroll_down(PAGE_SCROLL_STEP),
-                KeyCode::Esc => self.mode = InputMode::Normal,
+                KeyCode::Char('g') => self.pty.scroll_to_top(),
+                KeyCode::Char('G') => self.pty.scroll_to_bottom(),
+                KeyCode::Char('/') => self.start_search(false),
+                KeyCode::Char('?') => self.start_search(true),
+                KeyCode::Char('n') => self.pty.search_next(false),
+                KeyCode::Char('N') => self.pty.search_next(true),
+                KeyCode::Char('[') => {
+                    self.pty.enter_copy_mode();
+                    self.mode = InputMode::Copy;
+                },
+                KeyCode::Char('e') => {
+                    self.status = Some(
+                        self.export_scrollback()
+                            .unwrap_or_else(|e| format!("Failed to log the session: {e}")),
+                    );
+                },
+                KeyCode::Esc => self.exit_command_mode(),
                 KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    self.mode = InputMode::Normal;
+                    self.exit_command_mode();
+                },
+                _ => {},
+            }
+        }
+
+        Ok(App::RunningPty(self))
+    }
+
+    /// Handles keys while editing the scrollback search pattern.
+    fn handle_search_key_event(
+        mut self: Box<Self>,
+        event: &Event,
+        backward: bool,
+    ) -> App {
+        if let Event::Paste(to_paste) = event {
+            self.search_pattern.push_str(to_paste);
+        } else if let Event::Key(key) = event
+            && key.kind != KeyEventKind::Release
+        {
+            match key.code {
+                KeyCode::Enter => {
+                    self.status = Some(match self.pty.search(&self.search_pattern, backward) {
+                        Ok(0) => format!("Pattern not found: {}", self.search_pattern),
+                        Ok(found) => format!("{found} match(es)"),
+                        Err(e) => format!("Invalid pattern: {e}"),
+                    });
+                    self.mode = InputMode::Command;
+                },
+                KeyCode::Esc => self.mode = InputMode::Command,
+                KeyCode::Backspace => {
+                    self.search_pattern.pop();
                 },
+                KeyCode::Char(c) => self.search_pattern.push(c),
                 _ => {},
             }
         }
 
         App::RunningPty(self)
     }
+
+    /// Handles keys while in the copy mode: `h`/`j`/`k`/`l` (or arrows), `0`/`$`,
+    /// `PageUp`/`PageDown` and `g`/`G` move the cursor, `v`/`V` start a character/line
+    /// selection, `y` (or `Enter`) yanks the selection into the host clipboard.
+    fn handle_copy_key_event(
+        mut self: Box<Self>,
+        event: &Event,
+    ) -> color_eyre::Result<App> {
+        if let Event::Mouse(mouse) = event {
+            self.handle_mouse_event(*mouse)?;
+        } else if let Event::Key(key) = event
+            && key.kind != KeyEventKind::Release
+        {
+            match key.code {
+                KeyCode::Left | KeyCode::Char('h') => self.pty.copy_mode_move(CopyMotion::Left),
+                KeyCode::Right | KeyCode::Char('l') => self.pty.copy_mode_move(CopyMotion::Right),
+                KeyCode::Up | KeyCode::Char('k') => self.pty.copy_mode_move(CopyMotion::Up),
+                KeyCode::Down | KeyCode::Char('j') => self.pty.copy_mode_move(CopyMotion::Down),
+                KeyCode::Home | KeyCode::Char('0') => {
+                    self.pty.copy_mode_move(CopyMotion::LineStart);
+                },
+                KeyCode::End | KeyCode::Char('$') => self.pty.copy_mode_move(CopyMotion::LineEnd),
+                KeyCode::PageUp => self.pty.copy_mode_move(CopyMotion::PageUp),
+                KeyCode::PageDown => self.pty.copy_mode_move(CopyMotion::PageDown),
+                KeyCode::Char('g') => self.pty.copy_mode_move(CopyMotion::Top),
+                KeyCode::Char('G') => self.pty.copy_mode_move(CopyMotion::Bottom),
+                KeyCode::Char('v') => self.pty.copy_mode_toggle_selection(SelectionKind::Char),
+                KeyCode::Char('V') => self.pty.copy_mode_toggle_selection(SelectionKind::Line),
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    if let Some(text) = self.pty.copy_mode_selected_text() {
+                        self.status =
+                            Some(yank(&text).unwrap_or_else(|e| format!("Failed to yank: {e}")));
+                        self.pty.exit_copy_mode();
+                        self.mode = InputMode::Command;
+                    }
+                },
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.pty.exit_copy_mode();
+                    self.mode = InputMode::Command;
+                },
+                _ => {},
+            }
+        }
+
+        Ok(App::RunningPty(self))
+    }
+
+    /// Starts the session log in the 'Shell-Cell' home directory, dumping the whole
+    /// scrollback into it. Returns the status message.
+    fn export_scrollback(&mut self) -> color_eyre::Result<String> {
+        const LOGS_DIR: &str = "logs";
+
+        if self.pty.is_session_logged() {
+            return Ok("The session is already being logged".to_owned());
+        }
+        let dir = scell_home_dir()?.join(LOGS_DIR);
+        std::fs::create_dir_all(&dir)?;
+        let path = dir.join(format!(
+            "{}-{}.log",
+            self.container_id,
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        self.pty.log_session(&path, SessionLogFormat::Plain)?;
+        Ok(format!("Logging the session into '{}'", path.display()))
+    }
+
+    fn start_search(
+        &mut self,
+        backward: bool,
+    ) {
+        self.search_pattern.clear();
+        self.mode = InputMode::Search { backward };
+    }
+
+    fn exit_command_mode(&mut self) {
+        self.pty.clear_search();
+        self.status = None;
+        self.mode = InputMode::Normal;
+    }
+}
+
+/// Yanks the text into a file in the 'Shell-Cell' home directory and into the host
+/// clipboard. The host terminal does not acknowledge OSC 52, so the clipboard delivery
+/// cannot be confirmed, and the file is always written.
+/// Returns the status message.
+fn yank(text: &str) -> color_eyre::Result<String> {
+    const YANK_FILE: &str = "yank.txt";
+
+    let lines = text.lines().count();
+    let path = scell_home_dir()?.join(YANK_FILE);
+    std::fs::write(&path, text)?;
+    Ok(match Clipboard::copy_text(text) {
+        Ok(()) => {
+            format!(
+                "Yanked {lines} line(s) into '{}', sent to the clipboard (unconfirmed)",
+                path.display()
+            )
+        },
+        Err(e) => {
+            format!(
+                "Yanked {lines} line(s) into '{}', clipboard failed: {e}",
+                path.display()
+            )
+        },
+    })
 }
@@ -1,9 +1,14 @@
 use ratatui::{
+    layout::Rect,
     style::Style,
     widgets::{Block, Borders, Widget},
 };
+use tui_term::widget::{Cursor, PseudoTerminal};
 
-use crate::cli::run::app::running_pty::{InputMode, RunningPtyState};
+use crate::{
+    cli::run::app::running_pty::{InputMode, RunningPtyState},
+    pty::CursorShape,
+};
 
 impl Widget for &mut RunningPtyState {
     fn render(
@@ -20,17 +25,49 @@ impl Widget for &mut RunningPtyState {
                     "Ctrl-B: command mode | Ctrl-H: help".to_owned(),
                 )
             },
-            InputMode::Command => (Style::new().yellow(), " COMMAND ".to_owned()),
+            InputMode::Command => {
+                (
+                    Style::new().yellow(),
+                    self.status.as_ref().map_or_else(
+                        || " COMMAND ".to_owned(),
+                        |status| format!(" COMMAND | {status} "),
+                    ),
+                )
+            },
+            InputMode::Copy => {
+                (
+                    Style::new().light_cyan(),
+                    " COPY | v/V: select | y: yank | q: quit ".to_owned(),
+                )
+            },
+            InputMode::Search { backward } => {
+                (
+                    Style::new().yellow(),
+                    format!(
+                        " {}{}█ ",
+                        if backward { '?' } else { '/' },
+                        self.search_pattern
+                    ),
+                )
+            },
         };
 
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(border_style)
             .title(format!(
-                "{} | {} | {}{}",
+                "{} | {} | {}{}{}{}",
                 self.container_id,
                 self.target_name,
                 self.location.display(),
+                self.pty
+                    .title()
+                    .map(|title| format!(" | {title}"))
+                    .unwrap_or_default(),
+                self.pty
+                    .cwd()
+                    .map(|cwd| format!(" | 📂 {cwd}"))
+                    .unwrap_or_default(),
                 crate::debugger::Debugger::session_id()
                     .map(|id| format!(" | Debug Session: {id}"))
                     .unwrap_or_default()
@@ -41,6 +78,66 @@ impl Widget for &mut RunningPtyState {
 
         // set the proper size for the terminal screen
         self.pty.set_size(inner.height, inner.width);
-        tui_term::widget::PseudoTerminal::new(self.pty.screen()).render(inner, buf);
+        self.pty_area = inner;
+        PseudoTerminal::new(self.pty.screen())
+            .cursor(cursor(self.pty.cursor_shape()))
+            .render(inner, buf);
+
+        for m in self.pty.visible_search_matches() {
+            let style = if m.current {
+                Style::new().black().on_light_red()
+            } else {
+                Style::new().black().on_yellow()
+            };
+            let area = Rect::new(
+                inner.x.saturating_add(m.start),
+                inner.y.saturating_add(m.row),
+                m.end.saturating_sub(m.start),
+                1,
+            )
+            .intersection(inner);
+            buf.set_style(area, style);
+        }
+
+        if let Some(copy_mode) = self.pty.visible_copy_mode() {
+            for (row, start, end) in copy_mode.selection {
+                let area = Rect::new(
+                    inner.x.saturating_add(start),
+                    inner.y.saturating_add(row),
+                    end.saturating_sub(start),
+                    1,
+                )
+                .intersection(inner);
+                buf.set_style(area, Style::new().black().on_light_cyan());
+            }
+            if let Some((row, col)) = copy_mode.cursor {
+                let area = Rect::new(
+                    inner.x.saturating_add(col),
+                    inner.y.saturating_add(row),
+                    1,
+                    1,
+                )
+                .intersection(inner);
+                buf.set_style(area, Style::new().reversed());
+            }
+        }
+    }
+}
+
+/// Returns the cursor widget, drawing the cursor shape.
+fn cursor(shape: CursorShape) -> Cursor {
+    match shape {
+        CursorShape::Block => Cursor::default(),
+        CursorShape::Underline => {
+            Cursor::default()
+                .symbol("▁")
+                .overlay_style(Style::new().underlined())
+        },
+        // A bar could not be drawn over a character, so it is underlined there
+        CursorShape::Bar => {
+            Cursor::default()
+                .symbol("▏")
+                .overlay_style(Style::new().underlined())
+        },
     }
 }
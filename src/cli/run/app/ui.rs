@@ -20,7 +20,7 @@ impl Widget for &mut App {
             App::Preparing(state) => state.render(area, buf),
             App::RunningPty(state) => state.render(area, buf),
             App::HelpWindow(state) => state.render(area, buf),
-            App::Finished => {
+            App::Finished(exit_code) => {
                 let block = Block::default()
                     .borders(Borders::ALL)
                     .border_style(Style::new().light_magenta())
@@ -36,16 +36,32 @@ impl Widget for &mut App {
                 // Create a centered area using Layout
                 let vertical_layout = Layout::vertical([
                     Constraint::Percentage(50),
-                    Constraint::Length(2),
+                    Constraint::Length(3),
                     Constraint::Percentage(50),
                 ])
                 .split(inner);
 
+                let exit_status = match exit_code {
+                    Some(0) => {
+                        Line::from(Span::styled(
+                            "Shell exited with code 0",
+                            Style::default().green(),
+                        ))
+                    },
+                    Some(code) => {
+                        Line::from(Span::styled(
+                            format!("Shell exited with code {code}"),
+                            Style::default().red(),
+                        ))
+                    },
+                    None => Line::default(),
+                };
                 let text = vec![
                     Line::from(Span::styled(
                         "Finished 'Shell-Cell' session",
                         Style::default().add_modifier(Modifier::BOLD).green(),
                     )),
+                    exit_status,
                     Line::from(Span::styled(
                         "<Press any key to exit>",
                         Style::default().cyan(),
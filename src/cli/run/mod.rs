@@ -1,21 +1,35 @@
 mod app;
 
-use std::path::Path;
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 
 use crate::{
-    buildkit::BuildKitD,
+    buildkit::{BuildKitD, container_info::SCellContainerInfo},
     cli::{run::app::App, terminal::Terminal},
+    pty::{Pty, SessionLogFormat},
     scell::types::name::TargetName,
     scell_home_dir,
 };
 
+/// Files, the session output is additionally written to.
+#[derive(Default)]
+pub struct SessionOutput {
+    /// asciicast v2 recording.
+    pub record: Option<PathBuf>,
+    /// Session log, appended with the terminal output.
+    pub log: Option<(PathBuf, SessionLogFormat)>,
+}
+
 pub async fn run<P: AsRef<Path> + Send + 'static>(
     scell_path: P,
     target: Option<TargetName>,
     detach: bool,
     quiet: bool,
     global: bool,
-) -> color_eyre::Result<()> {
+    output: SessionOutput,
+) -> color_eyre::Result<ExitCode> {
     // When `--global` is set, the global blueprint in the Shell-Cell home directory is used,
     // ignoring any local `scell.cue`. Otherwise the path provided by the user is used as is.
     let scell_path = if global {
@@ -25,7 +39,40 @@ pub async fn run<P: AsRef<Path> + Send + 'static>(
     };
     let buildkit = BuildKitD::start().await?;
     let mut terminal = Terminal::new()?;
-    let res = App::run(&buildkit, scell_path, target, detach, quiet, &mut terminal).await;
+    terminal.enable_mouse_capture()?;
+    terminal.enable_focus_and_keyboard_enhancement()?;
+    let res = App::run(
+        &buildkit,
+        scell_path,
+        target,
+        detach,
+        quiet,
+        output,
+        &mut terminal,
+    )
+    .await;
     ratatui::try_restore()?;
-    res
+    // `scell` exits with the exit code of the shell, codes out of the `0..=255` range are
+    // reported as a generic failure
+    Ok(res?.map_or(ExitCode::SUCCESS, |code| {
+        u8::try_from(code).map_or(ExitCode::FAILURE, ExitCode::from)
+    }))
+}
+
+/// Runs the interactive session for the `pty`, already attached to the shell of the
+/// `container`, until it is finished. Used by `scell ls` to attach to the listed
+/// containers, so the `terminal` is left initialized afterwards.
+pub async fn attach(
+    buildkit: &BuildKitD,
+    pty: Pty,
+    container: &SCellContainerInfo,
+    terminal: &mut Terminal,
+) -> color_eyre::Result<()> {
+    terminal.enable_mouse_capture()?;
+    terminal.enable_focus_and_keyboard_enhancement()?;
+    let res = App::attach(buildkit, pty, container, terminal).await;
+    // The host terminal title, forwarded from the session, is restored on returning
+    crate::pty::Title::restore();
+    terminal.disable_session_input()?;
+    res.map(drop)
 }
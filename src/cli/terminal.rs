@@ -1,5 +1,11 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use ratatui::{self, Frame, crossterm, prelude::CrosstermBackend};
 
+/// Whether the keyboard enhancement flags were pushed, so they must be popped on the
+/// terminal restore.
+static KEYBOARD_ENHANCED: AtomicBool = AtomicBool::new(false);
+
 pub struct Terminal(ratatui::DefaultTerminal);
 
 impl Terminal {
@@ -19,6 +25,50 @@ impl Terminal {
             .map_err(|e| color_eyre::eyre::eyre!("{e}"))?;
         Ok(())
     }
+
+    /// Enables reporting of the mouse events, disabled on the terminal restore.
+    pub fn enable_mouse_capture(&mut self) -> color_eyre::Result<()> {
+        crossterm::execute!(self.0.backend_mut(), crossterm::event::EnableMouseCapture)?;
+        Ok(())
+    }
+
+    /// Enables reporting of the focus changes and, if the terminal supports the Kitty
+    /// keyboard protocol, reporting of the disambiguated key events with their types
+    /// (press, repeat, release). Both are disabled on the terminal restore.
+    /// The keyboard enhancement flags are pushed only once, if enabled repeatedly.
+    pub fn enable_focus_and_keyboard_enhancement(&mut self) -> color_eyre::Result<()> {
+        crossterm::execute!(self.0.backend_mut(), crossterm::event::EnableFocusChange)?;
+        if !KEYBOARD_ENHANCED.load(Ordering::Relaxed)
+            && crossterm::terminal::supports_keyboard_enhancement()?
+        {
+            crossterm::execute!(
+                self.0.backend_mut(),
+                crossterm::event::PushKeyboardEnhancementFlags(
+                    crossterm::event::KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                        | crossterm::event::KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+                )
+            )?;
+            KEYBOARD_ENHANCED.store(true, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    /// Disables the mouse capture, the focus reporting and the keyboard enhancement,
+    /// enabled for the shell session, when returning to the UI which does not use them.
+    pub fn disable_session_input(&mut self) -> color_eyre::Result<()> {
+        if KEYBOARD_ENHANCED.swap(false, Ordering::Relaxed) {
+            crossterm::execute!(
+                self.0.backend_mut(),
+                crossterm::event::PopKeyboardEnhancementFlags
+            )?;
+        }
+        crossterm::execute!(
+            self.0.backend_mut(),
+            crossterm::event::DisableMouseCapture,
+            crossterm::event::DisableFocusChange
+        )?;
+        Ok(())
+    }
 }
 
 impl Drop for Terminal {
@@ -52,11 +102,22 @@ fn try_restore() -> color_eyre::Result<()> {
     // disabling raw mode first is important as it has more side effects than leaving the
     // alternate screen buffer
     crossterm::terminal::disable_raw_mode()?;
+    // the keyboard enhancement flags are kept per screen, so they must be popped before
+    // leaving the alternate screen
+    if KEYBOARD_ENHANCED.swap(false, Ordering::Relaxed) {
+        crossterm::execute!(
+            std::io::stdout(),
+            crossterm::event::PopKeyboardEnhancementFlags
+        )?;
+    }
     crossterm::execute!(
         std::io::stdout(),
         crossterm::terminal::LeaveAlternateScreen,
-        crossterm::event::DisableBracketedPaste
+        crossterm::event::DisableBracketedPaste,
+        crossterm::event::DisableMouseCapture,
+        crossterm::event::DisableFocusChange
     )?;
+    crate::pty::Title::restore();
     Ok(())
 }
 
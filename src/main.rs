@@ -9,12 +9,16 @@ mod pty;
 mod scell;
 mod version_check;
 
-use std::path::PathBuf;
+use std::{path::PathBuf, process::ExitCode};
 
 use clap::Parser;
 use color_eyre::eyre::ContextCompat;
 
-use crate::{cli::Cli, debugger::Debugger};
+use crate::{
+    cli::Cli,
+    debugger::Debugger,
+    pty::{Clipboard, Pty, Title},
+};
 
 fn scell_home_dir() -> color_eyre::Result<PathBuf> {
     const SCELL_HOME_DIR: &str = ".scell";
@@ -26,8 +30,11 @@ fn scell_home_dir() -> color_eyre::Result<PathBuf> {
 }
 
 #[tokio::main]
-async fn main() -> color_eyre::Result<()> {
+async fn main() -> color_eyre::Result<ExitCode> {
     Debugger::init()?;
+    Clipboard::init()?;
+    Title::init()?;
+    Pty::init()?;
     match Cli::try_parse() {
         Ok(cli) => {
             color_eyre::config::HookBuilder::default()
@@ -35,8 +42,7 @@ async fn main() -> color_eyre::Result<()> {
                 .capture_span_trace_by_default(false)
                 .display_env_section(false)
                 .install()?;
-            cli.exec().await?;
-            Ok(())
+            cli.exec().await
         },
         Err(e) => e.exit(),
     }
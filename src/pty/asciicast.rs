@@ -0,0 +1,255 @@
+//! Recording and reading of terminal sessions in the asciicast v2 format
+//! <https://docs.asciinema.org/manual/asciicast/v2/>.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, LineWriter, Write},
+    path::Path,
+    time::{Duration, Instant, SystemTime},
+};
+
+const VERSION: u8 = 2;
+const OUTPUT_EVENT: &str = "o";
+const RESIZE_EVENT: &str = "r";
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Header {
+    pub version: u8,
+    pub width: u16,
+    pub height: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CastEvent {
+    /// Data written to the terminal.
+    Output(Duration, Vec<u8>),
+    /// Terminal resize to the `(height, width)` size.
+    Resize(Duration, u16, u16),
+}
+
+impl CastEvent {
+    pub fn time(&self) -> Duration {
+        match self {
+            Self::Output(t, _) | Self::Resize(t, ..) => *t,
+        }
+    }
+}
+
+/// Writes the terminal session into the asciicast v2 file, event by event, so the
+/// recording survives an abrupt session end.
+pub struct Recorder {
+    out: LineWriter<File>,
+    start: Instant,
+    /// asciicast events must be valid UTF-8 strings, so a character split between the
+    /// output chunks is carried over.
+    decoder: Utf8Decoder,
+}
+
+impl Recorder {
+    pub fn create(
+        path: &Path,
+        height: u16,
+        width: u16,
+    ) -> color_eyre::Result<Self> {
+        let mut out = LineWriter::new(File::create(path)?);
+        let header = Header {
+            version: VERSION,
+            width,
+            height,
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs()),
+        };
+        writeln!(out, "{}", serde_json::to_string(&header)?)?;
+        Ok(Self {
+            out,
+            start: Instant::now(),
+            decoder: Utf8Decoder::default(),
+        })
+    }
+
+    pub fn output(
+        &mut self,
+        bytes: &[u8],
+    ) -> color_eyre::Result<()> {
+        let data = self.decoder.decode(bytes);
+        self.write_event(OUTPUT_EVENT, &data)
+    }
+
+    pub fn resize(
+        &mut self,
+        height: u16,
+        width: u16,
+    ) -> color_eyre::Result<()> {
+        self.write_event(RESIZE_EVENT, &format!("{width}x{height}"))
+    }
+
+    fn write_event(
+        &mut self,
+        code: &str,
+        data: &str,
+    ) -> color_eyre::Result<()> {
+        if data.is_empty() {
+            return Ok(());
+        }
+        let time = self.start.elapsed().as_secs_f64();
+        writeln!(self.out, "{}", serde_json::to_string(&(time, code, data))?)?;
+        Ok(())
+    }
+}
+
+/// Reads the asciicast v2 file, skipping unsupported event types (e.g. input or
+/// markers).
+pub fn read(path: &Path) -> color_eyre::Result<(Header, Vec<CastEvent>)> {
+    parse(BufReader::new(File::open(path)?))
+}
+
+fn parse(reader: impl BufRead) -> color_eyre::Result<(Header, Vec<CastEvent>)> {
+    let mut lines = reader.lines();
+    let Some(header) = lines.next().transpose()? else {
+        color_eyre::eyre::bail!("asciicast file is empty, missing header");
+    };
+    let header: Header = serde_json::from_str(&header)?;
+    if header.version != VERSION {
+        color_eyre::eyre::bail!(
+            "unsupported asciicast version '{}', only version '{VERSION}' is supported",
+            header.version
+        );
+    }
+
+    let mut events = Vec::new();
+    for line in lines {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (time, code, data): (f64, String, String) = serde_json::from_str(&line)?;
+        let time = Duration::try_from_secs_f64(time)?;
+        match code.as_str() {
+            OUTPUT_EVENT => events.push(CastEvent::Output(time, data.into_bytes())),
+            RESIZE_EVENT => {
+                let (width, height) = data
+                    .split_once('x')
+                    .ok_or_else(|| color_eyre::eyre::eyre!("invalid resize event '{data}'"))?;
+                events.push(CastEvent::Resize(time, height.parse()?, width.parse()?));
+            },
+            _ => {},
+        }
+    }
+    Ok((header, events))
+}
+
+/// Lossy UTF-8 decoder of a chunked byte stream, which carries a character split between
+/// the chunks over to the next chunk.
+#[derive(Default)]
+pub struct Utf8Decoder {
+    /// Trailing bytes of an incomplete UTF-8 character from the previous chunk.
+    incomplete: Vec<u8>,
+}
+
+impl Utf8Decoder {
+    pub fn decode(
+        &mut self,
+        bytes: &[u8],
+    ) -> String {
+        self.incomplete.extend_from_slice(bytes);
+        let valid_len = self
+            .incomplete
+            .len()
+            .saturating_sub(incomplete_tail_len(&self.incomplete));
+        let tail = self.incomplete.split_off(valid_len);
+        let data = String::from_utf8_lossy(&self.incomplete).into_owned();
+        self.incomplete = tail;
+        data
+    }
+}
+
+/// Returns the length of the incomplete UTF-8 character at the end of the bytes, `0` if
+/// there is none. The bytes before it are not checked, so they could be invalid.
+fn incomplete_tail_len(bytes: &[u8]) -> usize {
+    // a UTF-8 character is at most 4 bytes long, so at most 3 bytes could be missing
+    (1..=3).find(|len| {
+        bytes
+            .len()
+            .checked_sub(*len)
+            .and_then(|start| bytes.get(start..))
+            .is_some_and(|tail| {
+                // the tail starts with the leading byte and ends too early
+                matches!(std::str::from_utf8(tail), Err(e) if e.valid_up_to() == 0 && e.error_len().is_none())
+            })
+    })
+    .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test]
+    fn record_and_read_test() {
+        let path = std::env::temp_dir().join(format!("scell_{}.cast", uuid::Uuid::now_v7()));
+        let mut recorder = Recorder::create(&path, 24, 80).unwrap();
+        recorder.output(b"hello\r\n").unwrap();
+        // "é" split between two chunks
+        recorder.output(b"caf\xC3").unwrap();
+        recorder.output(b"\xA9").unwrap();
+        recorder.resize(30, 100).unwrap();
+        drop(recorder);
+
+        let (header, events) = read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!((header.version, header.height, header.width), (2, 24, 80));
+        let events: Vec<_> = events
+            .into_iter()
+            .map(|e| {
+                match e {
+                    CastEvent::Output(_, data) => String::from_utf8(data).unwrap(),
+                    CastEvent::Resize(_, h, w) => format!("{h}:{w}"),
+                }
+            })
+            .collect();
+        assert_eq!(events, vec!["hello\r\n", "caf", "é", "30:100"]);
+    }
+
+    #[test_case(&[b"caf\xC3", b"\xA9"] => vec!["caf", "é"] ; "split character")]
+    #[test_case(&[b"a\xFFb\xE4\xB8", b"\xAD"] => vec!["a\u{FFFD}b", "中"] ; "invalid byte before split character")]
+    #[test_case(&[b"a\xC3", b"b"] => vec!["a", "\u{FFFD}b"] ; "broken character")]
+    fn utf8_decoder_test(chunks: &[&[u8]]) -> Vec<String> {
+        let mut decoder = Utf8Decoder::default();
+        chunks.iter().map(|c| decoder.decode(c)).collect()
+    }
+
+    #[test]
+    fn parse_test() {
+        let cast = indoc::indoc! {r#"
+            {"version": 2, "width": 10, "height": 3}
+            [0.5, "o", "ls\r\n"]
+            [1.0, "i", "q"]
+            [1.5, "m", ""]
+            [2.0, "r", "20x5"]
+        "#};
+        let (header, events) = parse(cast.as_bytes()).unwrap();
+        assert_eq!(header, Header {
+            version: 2,
+            width: 10,
+            height: 3,
+            timestamp: None,
+        });
+        assert_eq!(events, vec![
+            CastEvent::Output(Duration::from_millis(500), b"ls\r\n".to_vec()),
+            CastEvent::Resize(Duration::from_secs(2), 5, 20),
+        ]);
+    }
+
+    #[test]
+    fn parse_unsupported_version_test() {
+        let cast = r#"{"version": 1, "width": 10, "height": 3}"#;
+        assert!(parse(cast.as_bytes()).is_err());
+    }
+}
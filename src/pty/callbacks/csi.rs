@@ -1,74 +1,206 @@
-use std::sync::mpsc::Sender;
-
 use bytes::Bytes;
-use tui_term::vt100::Parser;
+use tokio::sync::mpsc::UnboundedSender;
+use tui_term::vt100::{MouseProtocolEncoding, MouseProtocolMode};
+
+use crate::pty::{
+    CursorShape,
+    callbacks::{apply, tabs::TabStops},
+};
 
 /// CBT - Cursor Backward Tabulation
 ///
-/// Moves the cursor `n` tab stops to the left. Default tab stops are every 8 columns.
+/// Moves the cursor `n` tab stops to the left.
 /// If the cursor would move past the leftmost column, it stays at column 0.
 ///
 /// <https://ghostty.org/docs/vt/csi/cbt>
 pub fn cbt(
     screen: &mut tui_term::vt100::Screen,
+    tab_stops: &TabStops,
     n: u16,
 ) {
-    const TAB_WIDTH: u16 = 8;
-    let (rows, cols) = screen.size();
-    let (cursor_row, cursor_col) = screen.cursor_position();
-
-    // Calculate how many full tab stops to move back (n extra, plus the partial one we're
-    // already in)
-    let tabs_back = n.saturating_add(u16::from(cursor_col % TAB_WIDTH == 0));
-    let col = cursor_col.saturating_sub(tabs_back.saturating_mul(TAB_WIDTH));
-    let tab_offset = col % TAB_WIDTH;
-    let col = col.saturating_sub(tab_offset);
-
-    let contents = screen.contents_formatted();
-    let mut parser = Parser::new(rows, cols, 0);
-    parser.process(&contents);
-    parser.process(
-        format!(
-            "\x1B[{};{}H",
-            cursor_row.saturating_add(1),
-            col.saturating_add(1)
-        )
-        .as_bytes(),
+    let (_, cursor_col) = screen.cursor_position();
+    let col = tab_stops.prev(cursor_col, n);
+    apply(
+        screen,
+        format!("\x1B[{}G", col.saturating_add(1)).as_bytes(),
     );
-    screen.clone_from(parser.screen());
 }
 
 /// CHT - Cursor Horizontal Tabulation
 ///
-/// Moves the cursor `n` tab stops to the right. Default tab stops are every 8 columns.
+/// Moves the cursor `n` tab stops to the right.
+/// If the cursor would move past the rightmost column, it stays at the last column.
 ///
-/// <https://ghostty.org/docs/vt/csi/cbt>
+/// <https://ghostty.org/docs/vt/csi/cht>
 pub fn cht(
     screen: &mut tui_term::vt100::Screen,
+    tab_stops: &TabStops,
     n: u16,
 ) {
-    const TAB_WIDTH: u16 = 8;
-    let (rows, cols) = screen.size();
+    let (_, cols) = screen.size();
+    let (_, cursor_col) = screen.cursor_position();
+    let col = tab_stops.next(cursor_col, cols, n);
+    apply(
+        screen,
+        format!("\x1B[{}G", col.saturating_add(1)).as_bytes(),
+    );
+}
+
+/// TBC - Tab Clear
+///
+/// Clears the tab stop at the cursor column (`0`) or all tab stops (`3`).
+///
+/// <https://ghostty.org/docs/vt/csi/tbc>
+pub fn tbc(
+    screen: &tui_term::vt100::Screen,
+    tab_stops: &mut TabStops,
+    mode: u16,
+) {
+    match mode {
+        0 => {
+            let (_, cols) = screen.size();
+            let (_, cursor_col) = screen.cursor_position();
+            tab_stops.clear(cursor_col, cols);
+        },
+        3 => tab_stops.clear_all(),
+        _ => {},
+    }
+}
+
+/// REP - Repeat Previous Character
+///
+/// Prints the character, preceding the cursor, `n` more times.
+///
+/// <https://ghostty.org/docs/vt/csi/rep>
+pub fn rep(
+    screen: &mut tui_term::vt100::Screen,
+    n: u16,
+) {
+    let (_, cols) = screen.size();
     let (cursor_row, cursor_col) = screen.cursor_position();
+    // The previous position, wrapping to the end of the previous line
+    let prev = |(row, col): (u16, u16)| {
+        if col > 0 {
+            Some((row, col.saturating_sub(1)))
+        } else if row > 0 && screen.row_wrapped(row.saturating_sub(1)) {
+            Some((row.saturating_sub(1), cols.saturating_sub(1)))
+        } else {
+            None
+        }
+    };
+    let Some(mut pos) = prev((cursor_row, cursor_col)) else {
+        return;
+    };
+    if screen
+        .cell(pos.0, pos.1)
+        .is_some_and(tui_term::vt100::Cell::is_wide_continuation)
+        && let Some(wide) = prev(pos)
+    {
+        pos = wide;
+    }
+    let Some(c) = screen
+        .cell(pos.0, pos.1)
+        .filter(|cell| cell.has_contents())
+        .map(|cell| cell.contents().to_owned())
+    else {
+        return;
+    };
+    apply(screen, c.repeat(n.max(1).into()).as_bytes());
+}
 
-    // Calculate how many full tab stops to move forward
-    let tabs_forward = n.saturating_add(1);
-    let col = cursor_col.saturating_add(tabs_forward.saturating_mul(TAB_WIDTH));
-    let tab_offset = col % TAB_WIDTH;
-    let col = col.saturating_sub(tab_offset);
+/// DECSCUSR - Set Cursor Style
+///
+/// Returns the cursor shape for the style, blinking is not distinguished.
+///
+/// <https://ghostty.org/docs/vt/csi/decscusr>
+pub fn decscusr(style: u16) -> Option<CursorShape> {
+    match style {
+        0..=2 => Some(CursorShape::Block),
+        3 | 4 => Some(CursorShape::Underline),
+        5 | 6 => Some(CursorShape::Bar),
+        _ => None,
+    }
+}
 
-    let contents = screen.contents_formatted();
-    let mut parser = Parser::new(rows, cols, 0);
-    parser.process(&contents);
-    parser.process(
-        format!(
-            "\x1B[{};{}H",
-            cursor_row.saturating_add(1),
-            col.saturating_add(1)
-        )
-        .as_bytes(),
-    );
-    screen.clone_from(parser.screen());
+/// DA1 - Primary Device Attributes
+///
+/// Responds with `ESC[?62;22c`, a VT220 conformance level with ANSI color support.
+///
+/// <https://ghostty.org/docs/vt/csi/da1>
+pub fn da1(sender: &UnboundedSender<Bytes>) {
+    drop(sender.send(b"\x1b[?62;22c".as_slice().into()));
+}
+
+/// DA2 - Secondary Device Attributes
+///
+/// Responds with `ESC[>1;10;0c`, a VT220 terminal type with firmware version 10.
+///
+/// <https://ghostty.org/docs/vt/csi/da2>
+pub fn da2(sender: &UnboundedSender<Bytes>) {
+    drop(sender.send(b"\x1b[>1;10;0c".as_slice().into()));
+}
+
+/// XTWINOPS - Report the text area (`18`) or the screen (`19`) size in characters
+///
+/// Responds with `ESC[8;rows;colst` or `ESC[9;rows;colst` respectively,
+/// which are the same, as the terminal occupies the whole screen.
+///
+/// <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h4-Functions-using-CSI-_-ordered-by-the-final-character-lparen-s-rparen:CSI-Ps;Ps;Ps-t.1EB0>
+pub fn xtwinops_size(
+    sender: &UnboundedSender<Bytes>,
+    screen: &tui_term::vt100::Screen,
+    op: u16,
+) {
+    let (rows, cols) = screen.size();
+    let code = if op == 18 { 8 } else { 9 };
+    drop(sender.send(format!("\x1b[{code};{rows};{cols}t").into_bytes().into()));
+}
+
+/// DECRQM - Request Mode (DEC private modes)
+///
+/// Responds with `ESC[?mode;value$y`, where value is `1` if the mode is set,
+/// `2` if it is reset and `0` if it is not recognized.
+///
+/// <https://ghostty.org/docs/vt/csi/decrqm>
+pub fn decrqm(
+    sender: &UnboundedSender<Bytes>,
+    screen: &tui_term::vt100::Screen,
+    focus_reporting: bool,
+    mode: u16,
+) {
+    let set = match mode {
+        1 => Some(screen.application_cursor()),
+        25 => Some(!screen.hide_cursor()),
+        47 | 1047 | 1049 => Some(screen.alternate_screen()),
+        66 => Some(screen.application_keypad()),
+        9 => Some(screen.mouse_protocol_mode() == MouseProtocolMode::Press),
+        1000 => Some(screen.mouse_protocol_mode() == MouseProtocolMode::PressRelease),
+        1002 => Some(screen.mouse_protocol_mode() == MouseProtocolMode::ButtonMotion),
+        1003 => Some(screen.mouse_protocol_mode() == MouseProtocolMode::AnyMotion),
+        1004 => Some(focus_reporting),
+        1005 => Some(screen.mouse_protocol_encoding() == MouseProtocolEncoding::Utf8),
+        1006 => Some(screen.mouse_protocol_encoding() == MouseProtocolEncoding::Sgr),
+        2004 => Some(screen.bracketed_paste()),
+        _ => None,
+    };
+    let value = match set {
+        Some(true) => 1,
+        Some(false) => 2,
+        None => 0,
+    };
+    drop(sender.send(format!("\x1b[?{mode};{value}$y").into_bytes().into()));
+}
+
+/// DECRQM - Request Mode (ANSI modes)
+///
+/// Responds with `ESC[mode;0$y`, as none of the ANSI modes are supported.
+///
+/// <https://ghostty.org/docs/vt/csi/decrqm>
+pub fn decrqm_ansi(
+    sender: &UnboundedSender<Bytes>,
+    mode: u16,
+) {
+    drop(sender.send(format!("\x1b[{mode};0$y").into_bytes().into()));
 }
 
 /// DSR - Device Status Report (operating status)
@@ -76,7 +208,7 @@ pub fn cht(
 /// Responds with `ESC[0n` to indicate the terminal is functioning normally.
 ///
 /// <https://ghostty.org/docs/vt/csi/dsr>
-pub fn dsr_status(sender: &Sender<Bytes>) {
+pub fn dsr_status(sender: &UnboundedSender<Bytes>) {
     drop(sender.send(b"\x1b[0n".as_slice().into()));
 }
 
@@ -86,7 +218,7 @@ pub fn dsr_status(sender: &Sender<Bytes>) {
 ///
 /// <https://ghostty.org/docs/vt/csi/dsr>
 pub fn dsr_cursor(
-    sender: &Sender<Bytes>,
+    sender: &UnboundedSender<Bytes>,
     screen: &tui_term::vt100::Screen,
 ) {
     let (row, col) = screen.cursor_position();
@@ -98,3 +230,15 @@ pub fn dsr_cursor(
         ),
     );
 }
+
+/// Kitty keyboard protocol flags query
+///
+/// Responds with `ESC[?flagsu` to report the currently active keyboard enhancement flags.
+///
+/// <https://sw.kovidgoyal.net/kitty/keyboard-protocol/#progressive-enhancement>
+pub fn kitty_keyboard_query(
+    sender: &UnboundedSender<Bytes>,
+    flags: u8,
+) {
+    drop(sender.send(format!("\x1b[?{flags}u").into_bytes().into()));
+}
@@ -1,16 +1,128 @@
 mod csi;
 mod esc;
+mod osc;
+mod tabs;
 
-use std::sync::mpsc::Sender;
+use std::cell::RefCell;
 
 use bytes::Bytes;
-use tui_term::vt100::Callbacks;
+use tokio::sync::mpsc::UnboundedSender;
+use tui_term::vt100::{Callbacks, Parser, Screen};
+
+use crate::pty::{CursorShape, callbacks::tabs::TabStops, keyboard::KittyKeyboard};
 
 /// `vt100::Callbacks` implementation, to properly handle unhandled by `vt100::Parser`
 /// escape codes.
-pub struct TerminalCallback(pub Sender<Bytes>);
+pub struct TerminalCallback {
+    /// Terminal's input, used to respond on the requests.
+    pub stdin: UnboundedSender<Bytes>,
+    /// Window title, reported with OSC 0 or OSC 2.
+    pub title: Option<String>,
+    /// Current working directory, reported with OSC 7.
+    pub cwd: Option<String>,
+    /// Focus reporting, enabled with DECSET 1004.
+    pub focus_reporting: bool,
+    /// Kitty keyboard protocol flags.
+    pub keyboard: KittyKeyboard,
+    /// Tab stops, set with HTS and cleared with TBC.
+    pub tab_stops: TabStops,
+    /// Cursor shape, set with DECSCUSR.
+    pub cursor_shape: CursorShape,
+    /// Only the screen is updated, the host is never touched: the clipboard requests are
+    /// ignored, the window title is not forwarded and nobody answers the requests.
+    /// Used to play back the recorded sessions.
+    pub passive: bool,
+}
+
+impl TerminalCallback {
+    pub fn new(stdin: UnboundedSender<Bytes>) -> Self {
+        Self {
+            stdin,
+            title: None,
+            cwd: None,
+            focus_reporting: false,
+            keyboard: KittyKeyboard::default(),
+            tab_stops: TabStops::default(),
+            cursor_shape: CursorShape::default(),
+            passive: false,
+        }
+    }
+
+    /// Returns the [`TerminalCallback::passive`] callback.
+    pub fn passive() -> Self {
+        // Nobody answers the requests of the recorded application
+        let (stdin, _) = tokio::sync::mpsc::unbounded_channel();
+        Self {
+            passive: true,
+            ..Self::new(stdin)
+        }
+    }
+
+    /// HT - Horizontal Tab
+    ///
+    /// `vt100::Parser` handles HT on its own, with the default tab stops every 8 columns,
+    /// so this is used only if custom tab stops are set.
+    ///
+    /// <https://ghostty.org/docs/vt/control/tab>
+    pub fn ht(parser: &mut Parser<Self>) {
+        let (_, cols) = parser.screen().size();
+        let (_, cursor_col) = parser.screen().cursor_position();
+        let col = parser.callbacks().tab_stops.next(cursor_col, cols, 1);
+        apply(
+            parser.screen_mut(),
+            format!("\x1B[{}G", col.saturating_add(1)).as_bytes(),
+        );
+    }
+}
+
+/// Applies the sequence to the screen in place, with the `vt100::Parser` implementation,
+/// so the callbacks could be implemented with the sequences, `vt100::Parser` already
+/// supports. The screen is moved into a scratch parser and back, so neither the screen is
+/// copied, nor its state (e.g. the scroll region, modes, scrollback) is lost.
+fn apply(
+    screen: &mut Screen,
+    seq: &[u8],
+) {
+    thread_local! {
+        static SCRATCH: RefCell<Parser> = RefCell::new(Parser::new(1, 1, 0));
+    }
+    SCRATCH.with_borrow_mut(|parser| {
+        std::mem::swap(parser.screen_mut(), screen);
+        parser.process(seq);
+        std::mem::swap(parser.screen_mut(), screen);
+    });
+}
 
 impl Callbacks for TerminalCallback {
+    fn set_window_title(
+        &mut self,
+        _: &mut tui_term::vt100::Screen,
+        title: &[u8],
+    ) {
+        self.title = osc::set_window_title(title, !self.passive);
+    }
+
+    fn copy_to_clipboard(
+        &mut self,
+        _: &mut tui_term::vt100::Screen,
+        ty: &[u8],
+        data: &[u8],
+    ) {
+        if !self.passive {
+            osc::copy_to_clipboard(ty, data);
+        }
+    }
+
+    fn paste_from_clipboard(
+        &mut self,
+        _: &mut tui_term::vt100::Screen,
+        ty: &[u8],
+    ) {
+        if !self.passive {
+            osc::paste_from_clipboard(&self.stdin, ty);
+        }
+    }
+
     fn unhandled_escape(
         &mut self,
         screen: &mut tui_term::vt100::Screen,
@@ -21,8 +133,10 @@ impl Callbacks for TerminalCallback {
         match (i1, i2, b) {
             // https://ghostty.org/docs/vt/esc/decaln
             (Some(b'#'), None, b'8') => esc::decaln(screen),
+            // https://ghostty.org/docs/vt/esc/ind
             (None, None, b'D') => esc::ind(screen),
-            (None, None, b'M') => esc::ri(screen),
+            // https://ghostty.org/docs/vt/esc/hts
+            (None, None, b'H') => esc::hts(screen, &mut self.tab_stops),
             _ => {},
         }
     }
@@ -41,14 +155,71 @@ impl Callbacks for TerminalCallback {
     ) {
         match (i1, i2, params, c) {
             // Cursor Backward Tabulation <https://ghostty.org/docs/vt/csi/cbt>
-            (None, None, &[&[n]], 'Z') => csi::cbt(screen, n),
+            (None, None, &[&[n]], 'Z') => csi::cbt(screen, &self.tab_stops, n),
             // Cursor Horizontal Tabulation <https://ghostty.org/docs/vt/csi/cht>
-            (None, None, &[&[n]], 'I') => csi::cht(screen, n),
+            (None, None, &[&[n]], 'I') => csi::cht(screen, &self.tab_stops, n),
+            // Tab Clear <https://ghostty.org/docs/vt/csi/tbc>
+            (None, None, &[&[mode]], 'g') => csi::tbc(screen, &mut self.tab_stops, mode),
+            // Repeat Previous Character <https://ghostty.org/docs/vt/csi/rep>
+            (None, None, &[&[n]], 'b') => csi::rep(screen, n),
+            // Set Cursor Style <https://ghostty.org/docs/vt/csi/decscusr>
+            (Some(b' '), None, &[&[style]], 'q') => {
+                if let Some(shape) = csi::decscusr(style) {
+                    self.cursor_shape = shape;
+                }
+            },
+            // Primary Device Attributes <https://ghostty.org/docs/vt/csi/da1>
+            (None, None, &[&[0]], 'c') => csi::da1(&self.stdin),
+            // Secondary Device Attributes <https://ghostty.org/docs/vt/csi/da2>
+            (Some(b'>'), None, &[&[0]], 'c') => csi::da2(&self.stdin),
+            // Window size in characters <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html>
+            (None, None, &[&[op @ (18 | 19)]], 't') => csi::xtwinops_size(&self.stdin, screen, op),
+            // Request Mode <https://ghostty.org/docs/vt/csi/decrqm>
+            (Some(b'?'), Some(b'$'), &[&[mode]], 'p') => {
+                csi::decrqm(&self.stdin, screen, self.focus_reporting, mode);
+            },
+            (Some(b'$'), None, &[&[mode]], 'p') => csi::decrqm_ansi(&self.stdin, mode),
             // Device Status Report (operating status) <https://ghostty.org/docs/vt/csi/dsr>
-            (None, None, &[&[5]], 'n') => csi::dsr_status(&self.0),
+            (None, None, &[&[5]], 'n') => csi::dsr_status(&self.stdin),
             // Device Status Report (cursor position) <https://ghostty.org/docs/vt/csi/dsr>
-            (None, None, &[&[6]], 'n') => csi::dsr_cursor(&self.0, screen),
+            (None, None, &[&[6]], 'n') => csi::dsr_cursor(&self.stdin, screen),
+            // Focus reporting <https://ghostty.org/docs/vt/reference#modes>
+            (Some(b'?'), None, params, 'h' | 'l') if params.contains(&[1004].as_slice()) => {
+                self.focus_reporting = c == 'h';
+            },
+            // Kitty keyboard protocol <https://sw.kovidgoyal.net/kitty/keyboard-protocol/>
+            (Some(b'?'), None, _, 'u') => {
+                csi::kitty_keyboard_query(
+                    &self.stdin,
+                    self.keyboard.flags(screen.alternate_screen()),
+                );
+            },
+            (Some(b'>'), None, &[&[flags]], 'u') => {
+                self.keyboard.push(screen.alternate_screen(), flags);
+            },
+            (Some(b'<'), None, &[&[n]], 'u') => self.keyboard.pop(screen.alternate_screen(), n),
+            (Some(b'='), None, &[&[flags]], 'u') => {
+                self.keyboard.set(screen.alternate_screen(), flags, 1);
+            },
+            (Some(b'='), None, &[&[flags], &[mode]], 'u') => {
+                self.keyboard.set(screen.alternate_screen(), flags, mode);
+            },
             _ => {},
         }
     }
+
+    /// This callback is called when the terminal receives an OSC sequence
+    /// (`\e]`) which is otherwise not implemented.
+    ///
+    /// <https://ghostty.org/docs/vt/concepts/sequences#osc>
+    fn unhandled_osc(
+        &mut self,
+        _: &mut tui_term::vt100::Screen,
+        params: &[&[u8]],
+    ) {
+        // Report Working Directory
+        if let [b"7", uri @ ..] = params {
+            self.cwd = osc::report_cwd(uri);
+        }
+    }
 }
@@ -0,0 +1,82 @@
+use bytes::Bytes;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::pty::{clipboard::Clipboard, title::Title};
+
+/// OSC 52 - Set Clipboard
+/// <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h3-Operating-System-Commands>
+pub fn copy_to_clipboard(
+    ty: &[u8],
+    data: &[u8],
+) {
+    if let Err(e) = Clipboard::copy(ty, data).map(drop) {
+        drop(crate::debugger::Debugger::log_debug(format!(
+            "Failed to copy to clipboard: {e}"
+        )));
+    }
+}
+
+/// OSC 52 - Query Clipboard
+/// <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h3-Operating-System-Commands>
+pub fn paste_from_clipboard(
+    stdin: &UnboundedSender<Bytes>,
+    ty: &[u8],
+) {
+    let stdin = stdin.clone();
+    if let Err(e) = Clipboard::paste(ty, move |response| drop(stdin.send(response.into()))) {
+        drop(crate::debugger::Debugger::log_debug(format!(
+            "Failed to read clipboard: {e}"
+        )));
+    }
+}
+
+/// OSC 0/2 - Set Window Title
+/// <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h3-Operating-System-Commands>
+///
+/// An empty title resets it. The title is forwarded to the host terminal, if `forward`
+/// is set.
+pub fn set_window_title(
+    title: &[u8],
+    forward: bool,
+) -> Option<String> {
+    let title = String::from_utf8_lossy(title).into_owned();
+    if forward {
+        Title::forward(&title);
+    }
+    (!title.is_empty()).then_some(title)
+}
+
+/// OSC 7 - Report Working Directory, in the `file://<hostname>/<path>` format.
+/// The `uri` is split by the `;` delimiter, so it is joined back.
+///
+/// An invalid URI resets the current working directory.
+pub fn report_cwd(uri: &[&[u8]]) -> Option<String> {
+    let uri = uri.join(&b';');
+    let rest = uri.strip_prefix(b"file://")?;
+    // skip the hostname
+    let path_start = rest.iter().position(|b| *b == b'/')?;
+    let path = rest.get(path_start..)?;
+    String::from_utf8(percent_decode(path)).ok()
+}
+
+fn percent_decode(input: &[u8]) -> Vec<u8> {
+    let mut res = Vec::with_capacity(input.len());
+    let mut iter = input.iter().copied();
+    while let Some(b) = iter.next() {
+        if b == b'%' {
+            let mut lookahead = iter.clone();
+            let decoded = lookahead
+                .next()
+                .zip(lookahead.next())
+                .and_then(|(h, l)| hex::decode([h, l]).ok())
+                .and_then(|v| v.first().copied());
+            if let Some(decoded) = decoded {
+                res.push(decoded);
+                iter = lookahead;
+                continue;
+            }
+        }
+        res.push(b);
+    }
+    res
+}
@@ -0,0 +1,99 @@
+use std::collections::BTreeSet;
+
+/// Default distance between tab stops.
+const TAB_WIDTH: u16 = 8;
+
+/// Horizontal tab stops, by default every 8 columns.
+/// Custom tab stops are set with HTS and cleared with TBC.
+#[derive(Default)]
+pub struct TabStops(Option<BTreeSet<u16>>);
+
+impl TabStops {
+    /// Returns `true` if no custom tab stops were set or cleared.
+    pub fn is_default(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Sets a tab stop at the column.
+    pub fn set(
+        &mut self,
+        col: u16,
+        cols: u16,
+    ) {
+        self.custom(cols).insert(col);
+    }
+
+    /// Clears a tab stop at the column.
+    pub fn clear(
+        &mut self,
+        col: u16,
+        cols: u16,
+    ) {
+        self.custom(cols).remove(&col);
+    }
+
+    /// Clears all tab stops.
+    pub fn clear_all(&mut self) {
+        self.0 = Some(BTreeSet::new());
+    }
+
+    /// Returns the column of the `n`th (at least the first) tab stop to the right of the
+    /// column, or the last column if there are not enough tab stops.
+    pub fn next(
+        &self,
+        col: u16,
+        cols: u16,
+        n: u16,
+    ) -> u16 {
+        let n = n.max(1);
+        let last = cols.saturating_sub(1);
+        let next = match &self.0 {
+            None => {
+                col.saturating_sub(col % TAB_WIDTH)
+                    .saturating_add(TAB_WIDTH.saturating_mul(n))
+            },
+            Some(stops) => {
+                stops
+                    .range(col.saturating_add(1)..)
+                    .nth(usize::from(n.saturating_sub(1)))
+                    .copied()
+                    .unwrap_or(last)
+            },
+        };
+        next.min(last)
+    }
+
+    /// Returns the column of the `n`th (at least the first) tab stop to the left of the
+    /// column, or the first column if there are not enough tab stops.
+    pub fn prev(
+        &self,
+        col: u16,
+        n: u16,
+    ) -> u16 {
+        let n = n.max(1);
+        match &self.0 {
+            None => {
+                let col = col.saturating_sub(1);
+                col.saturating_sub(col % TAB_WIDTH)
+                    .saturating_sub(TAB_WIDTH.saturating_mul(n.saturating_sub(1)))
+            },
+            Some(stops) => {
+                stops
+                    .range(..col)
+                    .nth_back(usize::from(n.saturating_sub(1)))
+                    .copied()
+                    .unwrap_or(0)
+            },
+        }
+    }
+
+    /// Materializes the default tab stops for the screen width, so they could be
+    /// modified.
+    fn custom(
+        &mut self,
+        cols: u16,
+    ) -> &mut BTreeSet<u16> {
+        self.0
+            .get_or_insert_with(|| (TAB_WIDTH..cols).step_by(TAB_WIDTH.into()).collect())
+    }
+}
@@ -0,0 +1,52 @@
+use config::Config;
+use serde::Deserialize;
+
+const ENV_VAR_PREFIX: &str = "SHELL_CELL_CLIPBOARD";
+
+#[derive(Debug, Deserialize)]
+pub struct ClipboardConfig {
+    /// Handle OSC 52 clipboard sequences from programs inside the container at all.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Allow programs inside the container to read the host clipboard.
+    /// Disabled by default, as any process inside the container could silently read
+    /// the host clipboard content.
+    #[serde(default)]
+    pub allow_read: bool,
+    #[serde(default)]
+    pub backend: ClipboardBackend,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardBackend {
+    /// Forwards OSC 52 sequences to the host terminal, which owns the clipboard.
+    #[default]
+    Terminal,
+    /// Uses a platform clipboard command (`pbcopy`, `wl-copy`, `xclip`, `clip.exe`).
+    Command,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Default for ClipboardConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            allow_read: false,
+            backend: ClipboardBackend::default(),
+        }
+    }
+}
+
+impl ClipboardConfig {
+    pub fn init() -> color_eyre::Result<Self> {
+        let res: ClipboardConfig = Config::builder()
+            .add_source(config::Environment::with_prefix(ENV_VAR_PREFIX).try_parsing(true))
+            .build()?
+            .try_deserialize()?;
+        Ok(res)
+    }
+}
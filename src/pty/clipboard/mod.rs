@@ -0,0 +1,298 @@
+//! Host clipboard integration for the OSC 52 sequences
+//! <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h3-Operating-System-Commands>.
+
+mod conf;
+
+use std::{
+    io::{Read as _, Write},
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+
+use self::conf::{ClipboardBackend, ClipboardConfig};
+
+/// Maximum length of the base64 encoded OSC 52 payload, larger payloads are dropped.
+const MAX_PAYLOAD_LEN: usize = 1024 * 1024;
+/// The clipboard command is killed, if it does not finish in time (e.g. `xclip` without
+/// any X selection owner).
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(2);
+const COMMAND_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+static CLIPBOARD: OnceLock<ClipboardConfig> = OnceLock::new();
+
+#[cfg(test)]
+thread_local! {
+    /// Number of the clipboard requests (both copy and paste) made on this thread.
+    pub static REQUESTS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+pub struct Clipboard;
+
+impl Clipboard {
+    pub fn init() -> color_eyre::Result<()> {
+        let config = ClipboardConfig::init()?;
+        CLIPBOARD
+            .set(config)
+            .map_err(|_| color_eyre::eyre::eyre!("Clipboard already initialised"))?;
+        Ok(())
+    }
+
+    fn config() -> &'static ClipboardConfig {
+        CLIPBOARD.get_or_init(ClipboardConfig::default)
+    }
+
+    /// Copies the text into the host clipboard with the configured backend.
+    /// It is a user request, not a program one, so it is not subject to the `enabled`
+    /// policy.
+    pub fn copy_text(text: &str) -> color_eyre::Result<()> {
+        write_clipboard(
+            Self::config().backend,
+            b"c",
+            BASE64_STANDARD.encode(text).as_bytes(),
+            &mut std::io::stdout().lock(),
+        )
+    }
+
+    /// Handles the OSC 52 set request, `data` is a base64 encoded payload.
+    /// Returns `false` if the clipboard integration is disabled.
+    ///
+    /// The clipboard command is run in the background, so it never blocks the caller.
+    pub fn copy(
+        ty: &[u8],
+        data: &[u8],
+    ) -> color_eyre::Result<bool> {
+        #[cfg(test)]
+        REQUESTS.set(REQUESTS.get().saturating_add(1));
+        copy_with(Self::config(), ty, data, &mut std::io::stdout().lock())
+    }
+
+    /// Handles the OSC 52 query request, passing the OSC 52 response, which should be
+    /// sent back to the program, to `on_paste`.
+    /// Returns `false` if clipboard reads are not allowed or not available.
+    ///
+    /// The host terminal cannot be queried from here, so the clipboard is always read
+    /// with the platform clipboard command, in the background, so it never blocks the
+    /// caller.
+    pub fn paste(
+        ty: &[u8],
+        on_paste: impl FnOnce(Vec<u8>) + Send + 'static,
+    ) -> color_eyre::Result<bool> {
+        #[cfg(test)]
+        REQUESTS.set(REQUESTS.get().saturating_add(1));
+        let config = Self::config();
+        if !config.enabled || !config.allow_read {
+            return Ok(false);
+        }
+        let Some((program, args)) = paste_command() else {
+            return Ok(false);
+        };
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdout = child.stdout.take();
+        let ty = ty.to_vec();
+        std::thread::spawn(move || {
+            let reader = std::thread::spawn(move || {
+                let mut payload = Vec::new();
+                if let Some(mut stdout) = stdout {
+                    stdout.read_to_end(&mut payload)?;
+                }
+                color_eyre::eyre::Ok(payload)
+            });
+            let res = wait_with_timeout(child).and_then(|status| {
+                color_eyre::eyre::ensure!(status.success(), "'{program}' failed with {status}");
+                reader
+                    .join()
+                    .map_err(|_| color_eyre::eyre::eyre!("Cannot read '{program}' output"))?
+            });
+            match res {
+                Ok(payload) => on_paste(osc52(&ty, BASE64_STANDARD.encode(payload).as_bytes())),
+                Err(e) => {
+                    drop(crate::debugger::Debugger::log_debug(format!(
+                        "Failed to read clipboard: {e}"
+                    )));
+                },
+            }
+        });
+        Ok(true)
+    }
+}
+
+fn copy_with(
+    config: &ClipboardConfig,
+    ty: &[u8],
+    data: &[u8],
+    terminal: &mut impl Write,
+) -> color_eyre::Result<bool> {
+    if !config.enabled {
+        return Ok(false);
+    }
+    write_clipboard(config.backend, ty, data, terminal)?;
+    Ok(true)
+}
+
+/// Writes the base64 encoded `data` into the host clipboard with the `backend`.
+fn write_clipboard(
+    backend: ClipboardBackend,
+    ty: &[u8],
+    data: &[u8],
+    terminal: &mut impl Write,
+) -> color_eyre::Result<()> {
+    color_eyre::eyre::ensure!(
+        data.len() <= MAX_PAYLOAD_LEN,
+        "Clipboard payload is too large, {} bytes, maximum is {MAX_PAYLOAD_LEN} bytes",
+        data.len()
+    );
+    match backend {
+        ClipboardBackend::Terminal => {
+            terminal.write_all(&osc52(ty, data))?;
+            terminal.flush()?;
+        },
+        ClipboardBackend::Command => {
+            let Some((program, args)) = copy_command() else {
+                color_eyre::eyre::bail!("No clipboard command available on this platform");
+            };
+            let payload = decode(data)?;
+            let mut child = Command::new(program)
+                .args(args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()?;
+            let stdin = child.stdin.take();
+            std::thread::spawn(move || {
+                // Killing the hung command closes its stdin, so the writer never hangs
+                let writer = std::thread::spawn(move || {
+                    if let Some(mut stdin) = stdin {
+                        drop(stdin.write_all(&payload));
+                    }
+                });
+                if let Err(e) = wait_with_timeout(child) {
+                    drop(crate::debugger::Debugger::log_debug(format!(
+                        "Failed to copy to clipboard: {e}"
+                    )));
+                }
+                drop(writer.join());
+            });
+        },
+    }
+    Ok(())
+}
+
+/// Decodes the base64 encoded OSC 52 payload.
+fn decode(data: &[u8]) -> color_eyre::Result<Vec<u8>> {
+    Ok(BASE64_STANDARD.decode(data)?)
+}
+
+/// Waits for the clipboard command to finish, killing it after the [`COMMAND_TIMEOUT`].
+fn wait_with_timeout(mut child: Child) -> color_eyre::Result<ExitStatus> {
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if start.elapsed() >= COMMAND_TIMEOUT {
+            child.kill()?;
+            child.wait()?;
+            color_eyre::eyre::bail!("Clipboard command timed out after {COMMAND_TIMEOUT:?}");
+        }
+        std::thread::sleep(COMMAND_POLL_INTERVAL);
+    }
+}
+
+fn osc52(
+    ty: &[u8],
+    data: &[u8],
+) -> Vec<u8> {
+    [b"\x1b]52;", ty, b";", data, b"\x07"].concat()
+}
+
+fn copy_command() -> Option<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        Some(("pbcopy", &[]))
+    } else if cfg!(target_os = "windows") {
+        Some(("clip.exe", &[]))
+    } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        Some(("wl-copy", &[]))
+    } else if std::env::var_os("DISPLAY").is_some() {
+        Some(("xclip", &["-selection", "clipboard"]))
+    } else {
+        None
+    }
+}
+
+fn paste_command() -> Option<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        Some(("pbpaste", &[]))
+    } else if cfg!(target_os = "windows") {
+        Some(("powershell.exe", &[
+            "-NoProfile",
+            "-Command",
+            "Get-Clipboard",
+        ]))
+    } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        Some(("wl-paste", &["--no-newline"]))
+    } else if std::env::var_os("DISPLAY").is_some() {
+        Some(("xclip", &["-selection", "clipboard", "-o"]))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    fn config(
+        enabled: bool,
+        backend: ClipboardBackend,
+    ) -> ClipboardConfig {
+        ClipboardConfig {
+            enabled,
+            allow_read: false,
+            backend,
+        }
+    }
+
+    #[test_case(true, b"aGVsbG8=" => (true, b"\x1b]52;c;aGVsbG8=\x07".to_vec()) ; "forward")]
+    #[test_case(false, b"aGVsbG8=" => (false, Vec::new()) ; "disabled")]
+    fn copy_forward_test(
+        enabled: bool,
+        data: &[u8],
+    ) -> (bool, Vec<u8>) {
+        let mut terminal = Vec::new();
+        let copied = copy_with(
+            &config(enabled, ClipboardBackend::Terminal),
+            b"c",
+            data,
+            &mut terminal,
+        )
+        .unwrap();
+        (copied, terminal)
+    }
+
+    #[test_case(ClipboardBackend::Terminal ; "terminal")]
+    #[test_case(ClipboardBackend::Command ; "command")]
+    fn copy_too_large_test(backend: ClipboardBackend) {
+        let data = vec![b'A'; MAX_PAYLOAD_LEN.saturating_add(4)];
+        let mut terminal = Vec::new();
+        assert!(copy_with(&config(true, backend), b"c", &data, &mut terminal).is_err());
+        assert!(terminal.is_empty());
+    }
+
+    #[test_case(b"aGVsbG8=" => Some(b"hello".to_vec()) ; "valid")]
+    #[test_case(b"" => Some(Vec::new()) ; "empty")]
+    #[test_case(b"aGVsbG8" => None ; "missing padding")]
+    #[test_case(b"!!!" => None ; "invalid")]
+    fn decode_test(data: &[u8]) -> Option<Vec<u8>> {
+        decode(data).ok()
+    }
+}
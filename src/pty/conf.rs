@@ -0,0 +1,33 @@
+use config::Config;
+use serde::Deserialize;
+
+const ENV_VAR_PREFIX: &str = "SHELL_CELL_PTY";
+
+#[derive(Debug, Deserialize)]
+pub struct PtyConfig {
+    /// Number of lines, kept in the terminal scrollback.
+    #[serde(default = "default_scrollback")]
+    pub scrollback: usize,
+}
+
+fn default_scrollback() -> usize {
+    5000
+}
+
+impl Default for PtyConfig {
+    fn default() -> Self {
+        Self {
+            scrollback: default_scrollback(),
+        }
+    }
+}
+
+impl PtyConfig {
+    pub fn init() -> color_eyre::Result<Self> {
+        let res: PtyConfig = Config::builder()
+            .add_source(config::Environment::with_prefix(ENV_VAR_PREFIX).try_parsing(true))
+            .build()?
+            .try_deserialize()?;
+        Ok(res)
+    }
+}
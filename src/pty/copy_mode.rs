@@ -0,0 +1,217 @@
+//! `tmux`-like copy mode: a cursor, moving over the terminal history, and a selection
+//! made with it.
+
+use tui_term::vt100::Screen;
+
+use crate::pty::search;
+
+/// A position in the terminal history, the line index starts from the oldest
+/// scrollback line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HistoryPos {
+    pub line: usize,
+    pub col: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionKind {
+    /// Selects characters between the anchor and the cursor.
+    Char,
+    /// Selects whole lines between the anchor and the cursor.
+    Line,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyMotion {
+    Left,
+    Right,
+    Up,
+    Down,
+    LineStart,
+    LineEnd,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+}
+
+pub struct CopyMode {
+    /// Snapshot of the terminal, taken on entering the copy mode, so the new output does
+    /// not move the history lines under the cursor and the selection.
+    screen: Screen,
+    scrollback_len: usize,
+    cursor: HistoryPos,
+    /// The selection start, if the selection was started.
+    anchor: Option<(HistoryPos, SelectionKind)>,
+}
+
+impl CopyMode {
+    /// Starts the copy mode with the cursor placed at the terminal cursor position.
+    pub fn new(
+        screen: &Screen,
+        scrollback_len: usize,
+    ) -> Self {
+        let (row, col) = screen.cursor_position();
+        let line = scrollback_len.saturating_add(row.into());
+        Self {
+            screen: screen.clone(),
+            scrollback_len,
+            cursor: HistoryPos { line, col },
+            anchor: None,
+        }
+    }
+
+    pub fn screen(&self) -> &Screen {
+        &self.screen
+    }
+
+    pub fn screen_mut(&mut self) -> &mut Screen {
+        &mut self.screen
+    }
+
+    /// Returns the number of the scrollback lines in the snapshot.
+    pub fn scrollback_len(&self) -> usize {
+        self.scrollback_len
+    }
+
+    /// Resizes the snapshot, keeping it in sync with the terminal size.
+    pub fn set_size(
+        &mut self,
+        height: u16,
+        width: u16,
+    ) {
+        self.screen.set_size(height, width);
+        self.scrollback_len = search::scrollback_len(&mut self.screen);
+    }
+
+    pub fn cursor(&self) -> HistoryPos {
+        self.cursor
+    }
+
+    pub fn move_cursor(
+        &mut self,
+        motion: CopyMotion,
+    ) {
+        let (rows, cols) = self.screen.size();
+        let last_line = self
+            .scrollback_len
+            .saturating_add(rows.saturating_sub(1).into());
+        let last_col = cols.saturating_sub(1);
+        let HistoryPos { line, col } = self.cursor;
+        self.cursor = match motion {
+            CopyMotion::Left => {
+                HistoryPos {
+                    line,
+                    col: col.saturating_sub(1),
+                }
+            },
+            CopyMotion::Right => {
+                HistoryPos {
+                    line,
+                    col: col.saturating_add(1).min(last_col),
+                }
+            },
+            CopyMotion::Up => {
+                HistoryPos {
+                    line: line.saturating_sub(1),
+                    col,
+                }
+            },
+            CopyMotion::Down => {
+                HistoryPos {
+                    line: line.saturating_add(1).min(last_line),
+                    col,
+                }
+            },
+            CopyMotion::LineStart => HistoryPos { line, col: 0 },
+            CopyMotion::LineEnd => {
+                HistoryPos {
+                    line,
+                    col: last_col,
+                }
+            },
+            CopyMotion::PageUp => {
+                HistoryPos {
+                    line: line.saturating_sub(rows.into()),
+                    col,
+                }
+            },
+            CopyMotion::PageDown => {
+                HistoryPos {
+                    line: line.saturating_add(rows.into()).min(last_line),
+                    col,
+                }
+            },
+            CopyMotion::Top => HistoryPos { line: 0, col: 0 },
+            CopyMotion::Bottom => {
+                HistoryPos {
+                    line: last_line,
+                    col: 0,
+                }
+            },
+        };
+    }
+
+    /// Starts the selection of the provided kind at the cursor, or clears it, if the
+    /// selection of the same kind was already started.
+    pub fn toggle_selection(
+        &mut self,
+        kind: SelectionKind,
+    ) {
+        self.anchor = match self.anchor {
+            Some((_, k)) if k == kind => None,
+            Some((anchor, _)) => Some((anchor, kind)),
+            None => Some((self.cursor, kind)),
+        };
+    }
+
+    /// Returns the ordered selection bounds, both inclusive.
+    pub fn selection(&self) -> Option<(HistoryPos, HistoryPos, SelectionKind)> {
+        let (anchor, kind) = self.anchor?;
+        let (start, end) = if anchor <= self.cursor {
+            (anchor, self.cursor)
+        } else {
+            (self.cursor, anchor)
+        };
+        Some((start, end, kind))
+    }
+
+    /// Returns the selected columns range (end exclusive) of the history line, if the
+    /// line is selected.
+    pub fn selected_cols(
+        &self,
+        line: usize,
+        cols: u16,
+    ) -> Option<(u16, u16)> {
+        let (start, end, kind) = self.selection()?;
+        if line < start.line || line > end.line {
+            return None;
+        }
+        match kind {
+            SelectionKind::Line => Some((0, cols)),
+            SelectionKind::Char => {
+                let from = if line == start.line { start.col } else { 0 };
+                let to = if line == end.line {
+                    end.col.saturating_add(1)
+                } else {
+                    cols
+                };
+                Some((from, to.min(cols)))
+            },
+        }
+    }
+
+    /// Returns the selected text, with the trailing whitespaces of each line trimmed.
+    pub fn selected_text(&self) -> Option<String> {
+        let (start, end, _) = self.selection()?;
+        let (_, cols) = self.screen.size();
+        let history = search::history(&self.screen);
+        let lines: Vec<_> = (start.line..=end.line)
+            .filter_map(|line| {
+                let (from, to) = self.selected_cols(line, cols)?;
+                Some(history.get(line)?.text_between(from, to).trim_end())
+            })
+            .collect();
+        Some(lines.join("\n"))
+    }
+}
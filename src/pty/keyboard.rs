@@ -0,0 +1,141 @@
+//! Kitty keyboard protocol support.
+//!
+//! <https://sw.kovidgoyal.net/kitty/keyboard-protocol/>
+
+use terminput::{Encoding, KeyEventKind, KittyFlags};
+
+/// Maximum depth of the flags stack, the oldest entries are evicted on overflow.
+const MAX_STACK_DEPTH: usize = 8;
+
+/// Progressive enhancement flags, defined by the protocol.
+const DISAMBIGUATE_ESCAPE_CODES: u8 = 0b1;
+const REPORT_EVENT_TYPES: u8 = 0b10;
+const REPORT_ALTERNATE_KEYS: u8 = 0b100;
+const REPORT_ALL_KEYS_AS_ESCAPE_CODES: u8 = 0b1000;
+const ALL_FLAGS: u16 = 0b1_1111;
+
+/// Keyboard enhancement flags, requested by the application inside the terminal.
+/// The main and the alternate screens keep their own flags stacks.
+#[derive(Default)]
+pub struct KittyKeyboard {
+    main: Vec<u8>,
+    alternate: Vec<u8>,
+}
+
+impl KittyKeyboard {
+    /// Returns the currently active flags.
+    pub fn flags(
+        &self,
+        alternate: bool,
+    ) -> u8 {
+        let stack = if alternate {
+            &self.alternate
+        } else {
+            &self.main
+        };
+        stack.last().copied().unwrap_or_default()
+    }
+
+    /// `CSI > flags u` - pushes the flags onto the stack.
+    pub fn push(
+        &mut self,
+        alternate: bool,
+        flags: u16,
+    ) {
+        let stack = self.stack_mut(alternate);
+        if stack.len() >= MAX_STACK_DEPTH {
+            stack.remove(0);
+        }
+        stack.push(to_flags(flags));
+    }
+
+    /// `CSI < n u` - pops `n` entries from the stack, `0` is treated as `1`.
+    pub fn pop(
+        &mut self,
+        alternate: bool,
+        n: u16,
+    ) {
+        let stack = self.stack_mut(alternate);
+        stack.truncate(stack.len().saturating_sub(n.max(1).into()));
+    }
+
+    /// `CSI = flags ; mode u` - replaces (`1`), sets (`2`) or resets (`3`) the currently
+    /// active flags.
+    pub fn set(
+        &mut self,
+        alternate: bool,
+        flags: u16,
+        mode: u16,
+    ) {
+        let flags = to_flags(flags);
+        let stack = self.stack_mut(alternate);
+        let current = stack.last().copied().unwrap_or_default();
+        let flags = match mode {
+            2 => current | flags,
+            3 => current & !flags,
+            _ => flags,
+        };
+        if let Some(last) = stack.last_mut() {
+            *last = flags;
+        } else {
+            stack.push(flags);
+        }
+    }
+
+    fn stack_mut(
+        &mut self,
+        alternate: bool,
+    ) -> &mut Vec<u8> {
+        if alternate {
+            &mut self.alternate
+        } else {
+            &mut self.main
+        }
+    }
+}
+
+fn to_flags(flags: u16) -> u8 {
+    u8::try_from(flags & ALL_FLAGS).unwrap_or_default()
+}
+
+/// Returns the key events encoding, matching the flags.
+pub fn encoding(flags: u8) -> Encoding {
+    const MAPPING: [(u8, KittyFlags); 4] = [
+        (
+            DISAMBIGUATE_ESCAPE_CODES,
+            KittyFlags::DISAMBIGUATE_ESCAPE_CODES,
+        ),
+        (REPORT_EVENT_TYPES, KittyFlags::REPORT_EVENT_TYPES),
+        (REPORT_ALTERNATE_KEYS, KittyFlags::REPORT_ALTERNATE_KEYS),
+        (
+            REPORT_ALL_KEYS_AS_ESCAPE_CODES,
+            KittyFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES,
+        ),
+    ];
+
+    if flags == 0 {
+        return Encoding::Xterm;
+    }
+    Encoding::Kitty(
+        MAPPING
+            .into_iter()
+            .filter(|(bit, _)| flags & bit != 0)
+            .fold(KittyFlags::empty(), |acc, (_, flag)| acc | flag),
+    )
+}
+
+/// Returns the kind, the key event should be reported with, or `None` if it should not
+/// be reported at all. Without the `REPORT_EVENT_TYPES` flag, releases are dropped and
+/// repeats are reported as presses.
+pub fn event_kind(
+    flags: u8,
+    kind: KeyEventKind,
+) -> Option<KeyEventKind> {
+    if flags & REPORT_EVENT_TYPES != 0 {
+        return Some(kind);
+    }
+    match kind {
+        KeyEventKind::Press | KeyEventKind::Repeat => Some(KeyEventKind::Press),
+        KeyEventKind::Release => None,
+    }
+}
@@ -2,66 +2,141 @@
 //! In the current implementation would follow the same API as <https://ghostty.org> has.
 //! The full reference to the ghostty terminal API documentation <https://ghostty.org/docs/vt>.
 
+mod asciicast;
 mod callbacks;
+mod clipboard;
+mod conf;
+mod copy_mode;
+mod keyboard;
+mod mouse;
+mod replay;
+mod search;
+mod session_log;
 #[cfg(test)]
 mod tests;
+mod title;
 
-use std::{
-    pin::Pin,
-    sync::mpsc::{Receiver, Sender},
-    time::Duration,
-};
+use std::{path::Path, pin::Pin, sync::OnceLock, time::Duration};
 
 use bollard::container::LogOutput;
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
-use tokio::io::{AsyncWrite, AsyncWriteExt};
-use tui_term::vt100::{Parser, Screen};
+use regex::Regex;
+use terminput::{Event, KeyEvent, MouseEvent};
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt},
+    sync::mpsc::{UnboundedReceiver, UnboundedSender, error::TryRecvError},
+};
+use tui_term::vt100::{MouseProtocolMode, Parser, Screen};
 
-use crate::pty::callbacks::TerminalCallback;
+use crate::pty::{
+    asciicast::Recorder, callbacks::TerminalCallback, conf::PtyConfig, copy_mode::CopyMode,
+    search::SearchMatch, session_log::SessionLog,
+};
+pub use crate::pty::{
+    asciicast::Utf8Decoder,
+    clipboard::Clipboard,
+    copy_mode::{CopyMotion, SelectionKind},
+    replay::Replay,
+    session_log::{AnsiStripper, SessionLogFormat},
+    title::Title,
+};
 
 type Output = Pin<Box<dyn Stream<Item = Result<LogOutput, bollard::errors::Error>> + Send>>;
 type Input = Pin<Box<dyn AsyncWrite + Send>>;
 
-const SCROLLBACK_WINDOW: usize = 5000;
+static PTY_CONFIG: OnceLock<PtyConfig> = OnceLock::new();
+
+/// Maximum number of output bytes, processed at once, so the fast output (e.g. `cat` of
+/// a big file) does not freeze the UI.
+const OUTPUT_BYTES_BUDGET: usize = 1024 * 1024;
 
 pub struct Pty {
-    stdin: Sender<Bytes>,
-    stdout: Receiver<Bytes>,
-    stderr: Receiver<Bytes>,
+    stdin: UnboundedSender<Bytes>,
+    /// Both `stdout` and `stderr`, in the order they were received.
+    output: UnboundedReceiver<Bytes>,
+    /// The output stream ended, so the process inside the terminal exited.
+    exited: bool,
     container_session_id: String,
     parser: Parser<TerminalCallback>,
+    recorder: Option<Recorder>,
+    session_log: Option<SessionLog>,
+    search: Option<Search>,
+    copy_mode: Option<CopyMode>,
+    /// Number of the scrollback lines, updated as the output is processed.
+    scrollback_len: usize,
+    /// Tracks escape sequences in the output, so HT inside of them (e.g. in the OSC
+    /// payload) is not treated as a tab.
+    output_escapes: AnsiStripper,
+}
+
+/// Cursor shape, requested by the application inside the terminal (DECSCUSR).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CursorShape {
+    #[default]
+    Block,
+    Underline,
+    Bar,
+}
+
+/// The last performed scrollback search.
+struct Search {
+    regex: Regex,
+    backward: bool,
+    matches: Vec<SearchMatch>,
+    /// Index of the match, the screen was scrolled to.
+    current: Option<usize>,
+}
+
+/// A search match, visible on the screen.
+pub struct VisibleMatch {
+    pub row: u16,
+    pub start: u16,
+    pub end: u16,
+    /// Whether it is the match, the screen was scrolled to.
+    pub current: bool,
+}
+
+/// The copy mode cursor and selection, visible on the screen.
+pub struct VisibleCopyMode {
+    /// The cursor `(row, col)` position, if it is visible.
+    pub cursor: Option<(u16, u16)>,
+    /// Selected `(row, start, end)` columns range, the end is exclusive.
+    pub selection: Vec<(u16, u16, u16)>,
 }
 
 impl Pty {
+    pub fn init() -> color_eyre::Result<()> {
+        let config = PtyConfig::init()?;
+        PTY_CONFIG
+            .set(config)
+            .map_err(|_| color_eyre::eyre::eyre!("Pty config already initialised"))?;
+        Ok(())
+    }
+
     pub fn new(
         container_session_id: String,
         mut output: Output,
         mut input: Input,
     ) -> Self {
-        let (stdout_in, stdout) = std::sync::mpsc::channel();
-        let (stderr_in, stderr) = std::sync::mpsc::channel();
+        let (output_in, output_out) = tokio::sync::mpsc::unbounded_channel();
         let _jh = tokio::spawn(async move {
-            while let Some(Ok(msg)) = output.next().await {
-                match msg {
-                    LogOutput::StdOut { message }
-                    | LogOutput::StdIn { message }
-                    | LogOutput::Console { message } => {
-                        drop(crate::debugger::Debugger::log_pty_stdout(&message));
-                        stdout_in.send(message)?;
-                    },
-                    LogOutput::StdErr { message } => {
-                        drop(crate::debugger::Debugger::log_pty_stdout(&message));
-                        stderr_in.send(message)?;
-                    },
-                }
+            while let Some(Ok(
+                LogOutput::StdOut { message }
+                | LogOutput::StdErr { message }
+                | LogOutput::StdIn { message }
+                | LogOutput::Console { message },
+            )) = output.next().await
+            {
+                drop(crate::debugger::Debugger::log_pty_stdout(&message));
+                output_in.send(message)?;
             }
             color_eyre::eyre::Ok(())
         });
 
-        let (stdin, stdin_out) = std::sync::mpsc::channel::<Bytes>();
+        let (stdin, mut stdin_out) = tokio::sync::mpsc::unbounded_channel::<Bytes>();
         let _jh = tokio::spawn(async move {
-            while let Ok(bytes) = stdin_out.recv() {
+            while let Some(bytes) = stdin_out.recv().await {
                 drop(crate::debugger::Debugger::log_pty_stdin(&bytes));
                 input.write_all(&bytes).await?;
                 input.flush().await?;
@@ -69,47 +144,287 @@ impl Pty {
             color_eyre::eyre::Ok(())
         });
 
-        let parser =
-            Parser::new_with_callbacks(24, 80, SCROLLBACK_WINDOW, TerminalCallback(stdin.clone()));
+        let parser = Parser::new_with_callbacks(
+            24,
+            80,
+            PTY_CONFIG.get_or_init(PtyConfig::default).scrollback,
+            TerminalCallback::new(stdin.clone()),
+        );
         Self {
             stdin,
-            stdout,
-            stderr,
+            output: output_out,
+            exited: false,
             container_session_id,
             parser,
+            recorder: None,
+            session_log: None,
+            search: None,
+            copy_mode: None,
+            scrollback_len: 0,
+            output_escapes: AnsiStripper::default(),
         }
     }
 
+    /// Starts recording of the terminal output into the asciicast v2 file.
+    pub fn record(
+        &mut self,
+        path: &Path,
+    ) -> color_eyre::Result<()> {
+        let (height, width) = self.size();
+        self.recorder = Some(Recorder::create(path, height, width)?);
+        Ok(())
+    }
+
     pub fn scroll_up(
         &mut self,
         lines: usize,
     ) {
-        let cur_scrollback = self.parser.screen().scrollback();
-        self.parser
-            .screen_mut()
-            .set_scrollback(cur_scrollback.saturating_add(lines));
+        let (screen, _) = self.view_mut();
+        screen.set_scrollback(screen.scrollback().saturating_add(lines));
     }
 
     pub fn scroll_down(
         &mut self,
         lines: usize,
     ) {
-        let cur_scrollback = self.parser.screen().scrollback();
-        self.parser
-            .screen_mut()
-            .set_scrollback(cur_scrollback.saturating_sub(lines));
+        let (screen, _) = self.view_mut();
+        screen.set_scrollback(screen.scrollback().saturating_sub(lines));
     }
 
     pub fn scroll_to_bottom(&mut self) {
-        self.parser.screen_mut().set_scrollback(0);
+        self.view_mut().0.set_scrollback(0);
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.view_mut().0.set_scrollback(usize::MAX);
+    }
+
+    /// Searches the scrollback and the screen with the regex `pattern`, scrolling to the
+    /// nearest match from the current position, in the search direction.
+    /// Forward search goes from the older lines to the newer ones, wrapping around.
+    ///
+    /// Returns the number of found matches.
+    pub fn search(
+        &mut self,
+        pattern: &str,
+        backward: bool,
+    ) -> color_eyre::Result<usize> {
+        let regex = Regex::new(pattern)?;
+        let (screen, scrollback_len) = self.view();
+        let matches = search::search(screen, &regex);
+        let (rows, _) = self.size();
+        let top = search::top_line(screen, scrollback_len);
+        let current = if backward {
+            let bottom = top.saturating_add(rows.into());
+            matches
+                .iter()
+                .rposition(|m| m.line < bottom)
+                .or(matches.len().checked_sub(1))
+        } else {
+            matches
+                .iter()
+                .position(|m| m.line >= top)
+                .or((!matches.is_empty()).then_some(0))
+        };
+        let found = matches.len();
+        self.search = Some(Search {
+            regex,
+            backward,
+            matches,
+            current,
+        });
+        self.scroll_to_current_match();
+        Ok(found)
+    }
+
+    /// Jumps to the next match of the last search, in the same direction as the search
+    /// was performed, or in the opposite one if `reverse` is set.
+    pub fn search_next(
+        &mut self,
+        reverse: bool,
+    ) {
+        let (screen, _) = self.view();
+        let matches = match &self.search {
+            // Pick up the output, which arrived after the search
+            Some(search) => search::search(screen, &search.regex),
+            None => return,
+        };
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        search.matches = matches;
+        let last = search.matches.len().checked_sub(1);
+        search.current = match (search.current, last) {
+            (_, None) => None,
+            (None, Some(_)) => Some(0),
+            (Some(i), Some(last)) if search.backward != reverse => {
+                Some(i.checked_sub(1).unwrap_or(last).min(last))
+            },
+            (Some(i), Some(last)) => Some(if i >= last { 0 } else { i.saturating_add(1) }),
+        };
+        self.scroll_to_current_match();
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search = None;
+    }
+
+    fn scroll_to_current_match(&mut self) {
+        let Some(line) = self
+            .search
+            .as_ref()
+            .and_then(|s| s.matches.get(s.current?))
+            .map(|m| m.line)
+        else {
+            return;
+        };
+        let (rows, _) = self.size();
+        let (screen, scrollback_len) = self.view_mut();
+        let top = search::top_line(screen, scrollback_len);
+        if line < top || line >= top.saturating_add(rows.into()) {
+            // Place the match in the middle of the screen
+            let top = line.saturating_sub((rows / 2).into());
+            let scrollback = search::scrollback_for_line(scrollback_len, top);
+            screen.set_scrollback(scrollback);
+        }
+    }
+
+    pub fn enter_copy_mode(&mut self) {
+        self.copy_mode = Some(CopyMode::new(self.parser.screen(), self.scrollback_len));
+    }
+
+    pub fn exit_copy_mode(&mut self) {
+        self.copy_mode = None;
+    }
+
+    /// Moves the copy mode cursor, scrolling the screen to keep it visible.
+    pub fn copy_mode_move(
+        &mut self,
+        motion: CopyMotion,
+    ) {
+        let Some(copy_mode) = &mut self.copy_mode else {
+            return;
+        };
+        copy_mode.move_cursor(motion);
+        let line = copy_mode.cursor().line;
+
+        let scrollback_len = copy_mode.scrollback_len();
+        let screen = copy_mode.screen_mut();
+        let (rows, _) = screen.size();
+        let top = search::top_line(screen, scrollback_len);
+        let top = if line < top {
+            line
+        } else if line >= top.saturating_add(rows.into()) {
+            line.saturating_sub(rows.saturating_sub(1).into())
+        } else {
+            return;
+        };
+        let scrollback = search::scrollback_for_line(scrollback_len, top);
+        screen.set_scrollback(scrollback);
+    }
+
+    pub fn copy_mode_toggle_selection(
+        &mut self,
+        kind: SelectionKind,
+    ) {
+        if let Some(copy_mode) = &mut self.copy_mode {
+            copy_mode.toggle_selection(kind);
+        }
+    }
+
+    /// Returns the text, selected in the copy mode.
+    pub fn copy_mode_selected_text(&self) -> Option<String> {
+        self.copy_mode.as_ref()?.selected_text()
+    }
+
+    /// Returns the copy mode cursor and selection, visible on the screen.
+    pub fn visible_copy_mode(&self) -> Option<VisibleCopyMode> {
+        let copy_mode = self.copy_mode.as_ref()?;
+        let (rows, cols) = self.size();
+        let top = search::top_line(copy_mode.screen(), copy_mode.scrollback_len());
+        let to_row = |line: usize| {
+            u16::try_from(line.checked_sub(top)?)
+                .ok()
+                .filter(|row| *row < rows)
+        };
+        let cursor = copy_mode.cursor();
+        Some(VisibleCopyMode {
+            cursor: to_row(cursor.line).map(|row| (row, cursor.col)),
+            selection: (0..rows)
+                .filter_map(|row| {
+                    let (start, end) =
+                        copy_mode.selected_cols(top.saturating_add(row.into()), cols)?;
+                    Some((row, start, end))
+                })
+                .collect(),
+        })
+    }
+
+    /// Returns matches of the last search, visible on the screen.
+    pub fn visible_search_matches(&self) -> Vec<VisibleMatch> {
+        let Some(search) = &self.search else {
+            return Vec::new();
+        };
+        let (rows, _) = self.size();
+        let (screen, scrollback_len) = self.view();
+        let top = search::top_line(screen, scrollback_len);
+        search
+            .matches
+            .iter()
+            .enumerate()
+            .filter_map(|(i, m)| {
+                let row = u16::try_from(m.line.checked_sub(top)?).ok()?;
+                (row < rows).then_some(VisibleMatch {
+                    row,
+                    start: m.start,
+                    end: m.end,
+                    current: search.current == Some(i),
+                })
+            })
+            .collect()
     }
 
     pub fn container_session_id(&self) -> &str {
         &self.container_session_id
     }
 
+    /// Returns the shown screen: the copy mode snapshot while in the copy mode, the live
+    /// one otherwise.
     pub fn screen(&self) -> &Screen {
-        self.parser.screen()
+        self.view().0
+    }
+
+    /// Returns the shown screen with the number of its scrollback lines.
+    fn view(&self) -> (&Screen, usize) {
+        match &self.copy_mode {
+            Some(copy_mode) => (copy_mode.screen(), copy_mode.scrollback_len()),
+            None => (self.parser.screen(), self.scrollback_len),
+        }
+    }
+
+    fn view_mut(&mut self) -> (&mut Screen, usize) {
+        match &mut self.copy_mode {
+            Some(copy_mode) => {
+                let scrollback_len = copy_mode.scrollback_len();
+                (copy_mode.screen_mut(), scrollback_len)
+            },
+            None => (self.parser.screen_mut(), self.scrollback_len),
+        }
+    }
+
+    /// Returns the window title, set by the application inside the terminal.
+    pub fn title(&self) -> Option<&str> {
+        self.parser.callbacks().title.as_deref()
+    }
+
+    /// Returns the current working directory, reported by the shell inside the terminal.
+    pub fn cwd(&self) -> Option<&str> {
+        self.parser.callbacks().cwd.as_deref()
+    }
+
+    /// Returns the cursor shape, requested by the application inside the terminal.
+    pub fn cursor_shape(&self) -> CursorShape {
+        self.parser.callbacks().cursor_shape
     }
 
     /// Returns the current size of the terminal.
@@ -123,19 +438,134 @@ impl Pty {
         height: u16,
         width: u16,
     ) {
+        if self.size() != (height, width) {
+            self.write_recording(|r| r.resize(height, width));
+        }
         self.parser.screen_mut().set_size(height, width);
+        self.scrollback_len = search::scrollback_len(self.parser.screen_mut());
+        if let Some(copy_mode) = &mut self.copy_mode {
+            copy_mode.set_size(height, width);
+        }
     }
 
-    /// Processes new updates from the `stdout` and `stderr` channels.
-    pub fn process_stdout_and_stderr(
+    /// Processes new updates from `stdout` and `stderr`, waiting for them no longer than
+    /// `timeout`. All pending updates are processed at once, within the bytes budget.
+    ///
+    /// While in the copy mode, the updates are still processed, but the copy mode shows
+    /// the snapshot of the terminal, taken on entering it.
+    ///
+    /// Returns `true` if the screen could have changed.
+    pub async fn process_stdout_and_stderr(
         &mut self,
         timeout: Duration,
+    ) -> bool {
+        let bytes = match tokio::time::timeout(timeout, self.output.recv()).await {
+            Ok(Some(bytes)) => bytes,
+            Ok(None) => {
+                self.exited = true;
+                return false;
+            },
+            Err(_) => return false,
+        };
+        self.process_output(&bytes);
+        let mut processed = bytes.len();
+        while processed < OUTPUT_BYTES_BUDGET {
+            match self.output.try_recv() {
+                Ok(bytes) => {
+                    self.process_output(&bytes);
+                    processed = processed.saturating_add(bytes.len());
+                },
+                Err(TryRecvError::Disconnected) => {
+                    self.exited = true;
+                    break;
+                },
+                Err(TryRecvError::Empty) => break,
+            }
+        }
+        self.copy_mode.is_none()
+    }
+
+    /// Returns `true` if the output stream ended and all of it was processed,
+    /// i.e. the process inside the terminal exited.
+    pub fn is_exited(&self) -> bool {
+        self.exited
+    }
+
+    fn process_output(
+        &mut self,
+        bytes: &[u8],
     ) {
-        if let Ok(bytes) = self.stdout.recv_timeout(timeout) {
-            self.parser.process(&bytes);
+        // `vt100::Parser` moves the cursor to the default tab stops on HT,
+        // so with custom tab stops, HT outside of escape sequences is handled separately.
+        // The tab stops are checked on every HT, as the same chunk could change them.
+        let output_escapes = &mut self.output_escapes;
+        let chunks = bytes.split(|b| output_escapes.advance(*b) && *b == b'\t');
+        for (i, chunk) in chunks.enumerate() {
+            if i > 0 {
+                if self.parser.callbacks().tab_stops.is_default() {
+                    self.parser.process(b"\t");
+                } else {
+                    TerminalCallback::ht(&mut self.parser);
+                }
+            }
+            self.parser.process(chunk);
         }
-        if let Ok(bytes) = self.stderr.recv_timeout(timeout) {
-            self.parser.process(&bytes);
+        self.scrollback_len = search::scrollback_len(self.parser.screen_mut());
+        self.write_recording(|r| r.output(bytes));
+        self.write_session_log(bytes);
+    }
+
+    /// Dumps the whole terminal history into the log file and keeps appending the
+    /// terminal output to it, while the session runs.
+    /// An existing log file is appended, not overwritten.
+    pub fn log_session(
+        &mut self,
+        path: &Path,
+        format: SessionLogFormat,
+    ) -> color_eyre::Result<()> {
+        self.session_log = Some(SessionLog::open(
+            path,
+            format,
+            self.parser.screen(),
+            self.scrollback_len,
+        )?);
+        Ok(())
+    }
+
+    /// Returns `true` if the session is being logged.
+    pub fn is_session_logged(&self) -> bool {
+        self.session_log.is_some()
+    }
+
+    /// Writes into the session log, if it is enabled.
+    /// A failed log is stopped, without interrupting the session.
+    fn write_session_log(
+        &mut self,
+        bytes: &[u8],
+    ) {
+        if let Some(log) = &mut self.session_log
+            && let Err(e) = log.output(bytes)
+        {
+            drop(crate::debugger::Debugger::log_debug(format!(
+                "Session log failed: {e}"
+            )));
+            self.session_log = None;
+        }
+    }
+
+    /// Writes into the session recording, if it is enabled.
+    /// A failed recording is stopped, without interrupting the session.
+    fn write_recording(
+        &mut self,
+        f: impl FnOnce(&mut Recorder) -> color_eyre::Result<()>,
+    ) {
+        if let Some(recorder) = &mut self.recorder
+            && let Err(e) = f(recorder)
+        {
+            drop(crate::debugger::Debugger::log_debug(format!(
+                "Session recording failed: {e}"
+            )));
+            self.recorder = None;
         }
     }
 
@@ -145,4 +575,86 @@ impl Pty {
     ) {
         drop(self.stdin.send(Bytes::copy_from_slice(bytes)));
     }
+
+    /// Sends the key event to the application inside the terminal, encoded with the
+    /// Kitty keyboard protocol, if the application requested it, otherwise with the
+    /// legacy xterm encoding.
+    ///
+    /// Returns `true` if anything was sent.
+    pub fn process_key_event(
+        &self,
+        event: KeyEvent,
+    ) -> bool {
+        let flags = self
+            .parser
+            .callbacks()
+            .keyboard
+            .flags(self.parser.screen().alternate_screen());
+        let Some(kind) = keyboard::event_kind(flags, event.kind) else {
+            return false;
+        };
+        let mut buf = [0u8; 32];
+        if let Ok(written) =
+            Event::Key(KeyEvent { kind, ..event }).encode(&mut buf, keyboard::encoding(flags))
+            && let Some(bytes) = buf.get(..written)
+        {
+            self.process_stdin(bytes);
+            return true;
+        }
+        false
+    }
+
+    /// Sends the pasted text to the application inside the terminal, wrapped into
+    /// `ESC[200~` and `ESC[201~`, if the application enabled bracketed paste (DECSET
+    /// 2004).
+    pub fn process_paste(
+        &self,
+        text: &str,
+    ) {
+        if self.parser.screen().bracketed_paste() {
+            // The pasted text must not be able to end the bracketed paste on its own
+            let text = text.replace("\x1b[201~", "");
+            self.process_stdin(format!("\x1b[200~{text}\x1b[201~").as_bytes());
+        } else {
+            self.process_stdin(text.as_bytes());
+        }
+    }
+
+    /// Reports the focus change to the application inside the terminal, if it enabled
+    /// focus reporting (DECSET 1004).
+    pub fn process_focus(
+        &self,
+        focused: bool,
+    ) {
+        if self.parser.callbacks().focus_reporting {
+            self.process_stdin(if focused { b"\x1b[I" } else { b"\x1b[O" });
+        }
+    }
+
+    /// Returns `true` if the application inside the terminal enabled mouse reporting.
+    pub fn is_mouse_reporting(&self) -> bool {
+        self.parser.screen().mouse_protocol_mode() != MouseProtocolMode::None
+    }
+
+    /// Reports the mouse event to the application inside the terminal, according to its
+    /// requested mouse tracking mode and encoding.
+    /// The event position must be relative to the terminal screen.
+    ///
+    /// Returns `false` if the application did not enable mouse reporting, so the event
+    /// could be handled differently (e.g. the wheel scrolls the scrollback).
+    pub fn process_mouse_event(
+        &self,
+        event: MouseEvent,
+    ) -> bool {
+        let screen = self.parser.screen();
+        if !self.is_mouse_reporting() {
+            return false;
+        }
+        if mouse::is_reported(screen.mouse_protocol_mode(), event.kind)
+            && let Some(bytes) = mouse::encode(screen.mouse_protocol_encoding(), event)
+        {
+            drop(self.stdin.send(bytes.into()));
+        }
+        true
+    }
 }
@@ -0,0 +1,114 @@
+//! Mouse reporting, requested by the application running inside the PTY terminal.
+//! <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html#h2-Mouse-Tracking>
+
+use terminput::{
+    Encoding, Event, KeyModifiers, MouseButton, MouseEvent, MouseEventKind, ScrollDirection,
+};
+use tui_term::vt100::{MouseProtocolEncoding, MouseProtocolMode};
+
+/// Offset, added to every value of the legacy (X10) mouse encoding, to keep the bytes
+/// printable.
+const LEGACY_OFFSET: u32 = 32;
+
+/// Returns `true` if the event must be reported under the provided mouse tracking
+/// mode.
+///
+/// - `9` (X10) reports button presses only.
+/// - `1000` (VT200) reports presses, releases and the wheel.
+/// - `1002` additionally reports motion while a button is pressed.
+/// - `1003` additionally reports any motion.
+pub fn is_reported(
+    mode: MouseProtocolMode,
+    kind: MouseEventKind,
+) -> bool {
+    match mode {
+        MouseProtocolMode::None => false,
+        MouseProtocolMode::Press => matches!(kind, MouseEventKind::Down(_)),
+        MouseProtocolMode::PressRelease => {
+            matches!(
+                kind,
+                MouseEventKind::Down(_) | MouseEventKind::Up(_) | MouseEventKind::Scroll(_)
+            )
+        },
+        MouseProtocolMode::ButtonMotion => !matches!(kind, MouseEventKind::Moved),
+        MouseProtocolMode::AnyMotion => true,
+    }
+}
+
+/// Encodes the mouse event with the provided encoding, the event position must be
+/// relative to the PTY terminal screen.
+/// Returns `None` if the event position cannot be represented with the encoding.
+pub fn encode(
+    encoding: MouseProtocolEncoding,
+    event: MouseEvent,
+) -> Option<Vec<u8>> {
+    match encoding {
+        // `1006` SGR encoding
+        MouseProtocolEncoding::Sgr => {
+            let mut buf = [0u8; 32];
+            let written = Event::Mouse(event).encode(&mut buf, Encoding::Xterm).ok()?;
+            buf.get(..written).map(<[u8]>::to_vec)
+        },
+        MouseProtocolEncoding::Default => {
+            let values = legacy_values(event)?;
+            values
+                .into_iter()
+                .map(|v| u8::try_from(v).ok())
+                .collect::<Option<Vec<_>>>()
+                .map(|bytes| [b"\x1b[M".as_slice(), &bytes].concat())
+        },
+        // `1005` UTF-8 encoding
+        MouseProtocolEncoding::Utf8 => {
+            let values = legacy_values(event)?;
+            let encoded: String = values
+                .into_iter()
+                .map(char::from_u32)
+                .collect::<Option<_>>()?;
+            Some([b"\x1b[M".as_slice(), encoded.as_bytes()].concat())
+        },
+    }
+}
+
+/// Button code, column and row of the legacy mouse encoding, with the printable offset
+/// applied.
+fn legacy_values(event: MouseEvent) -> Option<[u32; 3]> {
+    const RELEASE: u32 = 3;
+
+    let mut button = match event.kind {
+        MouseEventKind::Down(b) | MouseEventKind::Drag(b) => button_code(b),
+        MouseEventKind::Up(_) => RELEASE,
+        MouseEventKind::Moved => RELEASE.checked_add(32)?,
+        MouseEventKind::Scroll(ScrollDirection::Up) => 64,
+        MouseEventKind::Scroll(ScrollDirection::Down) => 65,
+        MouseEventKind::Scroll(ScrollDirection::Left) => 66,
+        MouseEventKind::Scroll(ScrollDirection::Right) => 67,
+    };
+    if matches!(event.kind, MouseEventKind::Drag(_)) {
+        button = button.checked_add(32)?;
+    }
+    if event.modifiers.intersects(KeyModifiers::SHIFT) {
+        button = button.checked_add(4)?;
+    }
+    if event.modifiers.intersects(KeyModifiers::ALT) {
+        button = button.checked_add(8)?;
+    }
+    if event.modifiers.intersects(KeyModifiers::CTRL) {
+        button = button.checked_add(16)?;
+    }
+    // positions are 1-based
+    let column = u32::from(event.column).checked_add(1)?;
+    let row = u32::from(event.row).checked_add(1)?;
+    Some([
+        button.checked_add(LEGACY_OFFSET)?,
+        column.checked_add(LEGACY_OFFSET)?,
+        row.checked_add(LEGACY_OFFSET)?,
+    ])
+}
+
+fn button_code(button: MouseButton) -> u32 {
+    match button {
+        MouseButton::Left | MouseButton::Unknown => 0,
+        MouseButton::Middle => 1,
+        MouseButton::Right => 2,
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    collections::VecDeque,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use tui_term::vt100::{Parser, Screen};
+
+use crate::pty::{
+    asciicast::{self, CastEvent},
+    callbacks::TerminalCallback,
+};
+
+/// Plays back the recorded asciicast v2 session through the same `vt100::Parser`, as
+/// the live session uses, with the [`TerminalCallback::passive`] callback, so the
+/// recorded sequences never touch the host (e.g. its clipboard or window title).
+pub struct Replay {
+    parser: Parser<TerminalCallback>,
+    events: VecDeque<CastEvent>,
+    start: Instant,
+    duration: Duration,
+}
+
+impl Replay {
+    pub fn open(path: &Path) -> color_eyre::Result<Self> {
+        let (header, events) = asciicast::read(path)?;
+        let duration = events.last().map(CastEvent::time).unwrap_or_default();
+        Ok(Self {
+            parser: Parser::new_with_callbacks(
+                header.height,
+                header.width,
+                0,
+                TerminalCallback::passive(),
+            ),
+            events: events.into(),
+            start: Instant::now(),
+            duration,
+        })
+    }
+
+    /// Applies all events, which are due at the current moment of the playback.
+    pub fn update(&mut self) {
+        let elapsed = self.start.elapsed();
+        while let Some(event) = self.events.pop_front() {
+            if event.time() > elapsed {
+                self.events.push_front(event);
+                break;
+            }
+            match event {
+                CastEvent::Output(_, data) => self.parser.process(&data),
+                CastEvent::Resize(_, height, width) => {
+                    self.parser.screen_mut().set_size(height, width);
+                },
+            }
+        }
+    }
+
+    pub fn screen(&self) -> &Screen {
+        self.parser.screen()
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.parser.callbacks().title.as_deref()
+    }
+
+    /// Returns the current playback position.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed().min(self.duration)
+    }
+
+    /// Returns the total duration of the recorded session.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pty::{asciicast::Recorder, clipboard::REQUESTS};
+
+    const CLIPBOARD_SEQS: &[u8] = b"\x1b]52;c;aGVsbG8=\x07\x1b]52;c;?\x07";
+
+    #[test]
+    fn replay_is_passive_test() {
+        // The live session handles the clipboard requests
+        let (stdin, _) = tokio::sync::mpsc::unbounded_channel();
+        let mut parser = Parser::new_with_callbacks(3, 10, 0, TerminalCallback::new(stdin));
+        REQUESTS.set(0);
+        parser.process(b"\x1b]52;c;?\x07");
+        assert_eq!(REQUESTS.get(), 1);
+
+        let path = std::env::temp_dir().join(format!("scell_{}.cast", uuid::Uuid::now_v7()));
+        let mut recorder = Recorder::create(&path, 3, 10).unwrap();
+        recorder.output(CLIPBOARD_SEQS).unwrap();
+        recorder.output(b"\x1b]2;title\x07hello").unwrap();
+        drop(recorder);
+        let mut replay = Replay::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        REQUESTS.set(0);
+        for _ in 0..1000 {
+            if replay.is_finished() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+            replay.update();
+        }
+        assert!(replay.is_finished());
+        assert_eq!(REQUESTS.get(), 0);
+        assert_eq!(replay.title(), Some("title"));
+        assert_eq!(replay.screen().contents(), "hello");
+    }
+}
@@ -0,0 +1,149 @@
+//! Regex search over the whole terminal history, the scrollback and the screen.
+
+use regex::Regex;
+use tui_term::vt100::Screen;
+
+/// A single text line of the terminal history.
+pub struct HistoryLine {
+    pub text: String,
+    /// Byte offset in `text` of every screen column, wide character continuations share
+    /// the offset of the wide character itself.
+    col_offsets: Vec<usize>,
+}
+
+impl HistoryLine {
+    /// Returns the text between the `start` column and the `end` column (exclusive).
+    pub fn text_between(
+        &self,
+        start: u16,
+        end: u16,
+    ) -> &str {
+        let offset = |col: u16| {
+            self.col_offsets
+                .get(usize::from(col))
+                .copied()
+                .unwrap_or(self.text.len())
+        };
+        self.text
+            .get(offset(start)..offset(end))
+            .unwrap_or_default()
+    }
+
+    /// Converts a byte offset in `text` into the screen column.
+    fn col(
+        &self,
+        offset: usize,
+    ) -> u16 {
+        let col = self.col_offsets.partition_point(|o| *o < offset);
+        u16::try_from(col).unwrap_or(u16::MAX)
+    }
+}
+
+/// A regex match in the terminal history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// Line index in the terminal history, starting from the oldest scrollback line.
+    pub line: usize,
+    /// The first column of the match.
+    pub start: u16,
+    /// The column after the last column of the match.
+    pub end: u16,
+}
+
+/// Returns the number of lines in the scrollback, keeping the scrollback position.
+/// `vt100::Screen` does not expose it, so the screen is scrolled to the top and back.
+pub fn scrollback_len(screen: &mut Screen) -> usize {
+    let scrollback = screen.scrollback();
+    screen.set_scrollback(usize::MAX);
+    let len = screen.scrollback();
+    screen.set_scrollback(scrollback);
+    len
+}
+
+/// Returns the history line index of the top screen row, for the current scrollback
+/// position.
+pub fn top_line(
+    screen: &Screen,
+    scrollback_len: usize,
+) -> usize {
+    scrollback_len.saturating_sub(screen.scrollback())
+}
+
+/// Returns the scrollback position, which shows the history line at the top row.
+pub fn scrollback_for_line(
+    scrollback_len: usize,
+    line: usize,
+) -> usize {
+    scrollback_len.saturating_sub(line)
+}
+
+/// Visits all lines of the terminal history, starting from the oldest one.
+/// The visitor receives the screen, scrolled so the line is visible, and the line's row.
+pub fn visit_history(
+    screen: &Screen,
+    mut visitor: impl FnMut(&Screen, u16),
+) {
+    let (rows, _) = screen.size();
+    let mut screen = screen.clone();
+    let scrollback_len = scrollback_len(&mut screen);
+    let total = scrollback_len.saturating_add(rows.into());
+
+    for line in 0..total {
+        // Scroll so the line is always at the top row, or below it for the last screen page
+        let scrollback = scrollback_len.saturating_sub(line);
+        screen.set_scrollback(scrollback);
+        let row = line.saturating_sub(scrollback_len);
+        visitor(&screen, u16::try_from(row).unwrap_or(u16::MAX));
+    }
+}
+
+/// Collects all lines of the terminal history, starting from the oldest one.
+pub fn history(screen: &Screen) -> Vec<HistoryLine> {
+    let (_, cols) = screen.size();
+    let mut res = Vec::new();
+    visit_history(screen, |screen, row| {
+        let mut text = String::new();
+        let mut col_offsets = Vec::with_capacity(cols.into());
+        for col in 0..cols {
+            match screen.cell(row, col) {
+                Some(cell) if cell.is_wide_continuation() => {
+                    let offset = col_offsets.last().copied().unwrap_or_default();
+                    col_offsets.push(offset);
+                },
+                Some(cell) if cell.has_contents() => {
+                    col_offsets.push(text.len());
+                    text.push_str(cell.contents());
+                },
+                _ => {
+                    col_offsets.push(text.len());
+                    text.push(' ');
+                },
+            }
+        }
+        res.push(HistoryLine { text, col_offsets });
+    });
+    res
+}
+
+/// Finds all regex matches in the terminal history, ordered from the oldest one.
+pub fn search(
+    screen: &Screen,
+    regex: &Regex,
+) -> Vec<SearchMatch> {
+    history(screen)
+        .iter()
+        .enumerate()
+        .flat_map(|(line, l)| {
+            regex
+                .find_iter(&l.text)
+                .filter(|m| !m.is_empty())
+                .map(move |m| {
+                    SearchMatch {
+                        line,
+                        start: l.col(m.start()),
+                        end: l.col(m.end()),
+                    }
+                })
+        })
+        .collect()
+}
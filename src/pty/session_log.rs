@@ -0,0 +1,190 @@
+//! Session log: a dump of the whole terminal history, continuously appended with the
+//! terminal output while the session runs.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    path::Path,
+};
+
+use tui_term::vt100::Screen;
+
+use crate::pty::search;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SessionLogFormat {
+    /// Plain text, without any escape sequences.
+    #[default]
+    Plain,
+    /// Text with ANSI attributes (colors, bold, etc.), readable with `less -R`.
+    Formatted,
+}
+
+pub struct SessionLog {
+    out: File,
+    /// Strips escape sequences from the output, for the plain format.
+    stripper: Option<AnsiStripper>,
+}
+
+impl SessionLog {
+    /// Opens the log file in the append mode and dumps the whole terminal history into
+    /// it.
+    /// The plain dump ends at the terminal cursor, so the appended output continues the
+    /// cursor line.
+    pub fn open(
+        path: &Path,
+        format: SessionLogFormat,
+        screen: &Screen,
+        scrollback_len: usize,
+    ) -> color_eyre::Result<Self> {
+        let mut out = OpenOptions::new().create(true).append(true).open(path)?;
+        let (_, cols) = screen.size();
+        match format {
+            SessionLogFormat::Plain => {
+                let (row, col) = screen.cursor_position();
+                let cursor_line = scrollback_len.saturating_add(row.into());
+                let history = search::history(screen);
+                let mut lines = history.iter().take(cursor_line.saturating_add(1));
+                let cursor_text = lines.next_back().map(|l| l.text_between(0, col));
+                for line in lines {
+                    writeln!(out, "{}", line.text.trim_end())?;
+                }
+                if let Some(text) = cursor_text {
+                    write!(out, "{text}")?;
+                }
+            },
+            SessionLogFormat::Formatted => {
+                let mut contents = Vec::new();
+                search::visit_history(screen, |screen, row| {
+                    if let Some(line) = screen.rows_formatted(0, cols).nth(row.into()) {
+                        contents.extend_from_slice(&line);
+                        // reset attributes at the end of each line
+                        contents.extend_from_slice(b"\x1b[m\n");
+                    }
+                });
+                out.write_all(&contents)?;
+            },
+        }
+        Ok(Self {
+            out,
+            stripper: (format == SessionLogFormat::Plain).then(AnsiStripper::default),
+        })
+    }
+
+    /// Appends the terminal output.
+    pub fn output(
+        &mut self,
+        bytes: &[u8],
+    ) -> color_eyre::Result<()> {
+        match &mut self.stripper {
+            Some(stripper) => self.out.write_all(&stripper.strip(bytes))?,
+            None => self.out.write_all(bytes)?,
+        }
+        Ok(())
+    }
+}
+
+/// Removes escape sequences and control characters (except new lines and tabs) from
+/// the terminal output.
+/// It keeps its state between chunks, so a sequence split between chunks is removed
+/// as well.
+#[derive(Default)]
+pub struct AnsiStripper {
+    state: StripState,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+enum StripState {
+    #[default]
+    Ground,
+    Escape,
+    /// Control Sequence Introducer, `ESC [`.
+    Csi,
+    /// Operating System Command, `ESC ]`, or other string sequences (DCS, APC, etc.).
+    String,
+    /// `ESC` inside the string sequence, which may start the `ESC \` terminator.
+    StringEscape,
+}
+
+impl AnsiStripper {
+    pub fn strip(
+        &mut self,
+        bytes: &[u8],
+    ) -> Vec<u8> {
+        let mut res = Vec::with_capacity(bytes.len());
+        for b in bytes.iter().copied() {
+            let text = self.advance(b);
+            // keep new lines and tabs, drop other control characters
+            if text && (matches!(b, b'\n' | b'\t') || !matches!(b, 0x00..0x20 | 0x7F)) {
+                res.push(b);
+            }
+        }
+        res
+    }
+
+    /// Advances the state with the next output byte.
+    /// Returns `true` if the byte is outside of escape sequences, i.e. it is a text or
+    /// a control character.
+    pub fn advance(
+        &mut self,
+        b: u8,
+    ) -> bool {
+        const ESC: u8 = 0x1B;
+        const BEL: u8 = 0x07;
+
+        let text = self.state == StripState::Ground && b != ESC;
+        self.state = match self.state {
+            StripState::Ground => {
+                match b {
+                    ESC => StripState::Escape,
+                    _ => StripState::Ground,
+                }
+            },
+            StripState::Escape => {
+                match b {
+                    b'[' => StripState::Csi,
+                    b']' | b'P' | b'X' | b'^' | b'_' => StripState::String,
+                    // intermediate bytes, e.g. `ESC ( B`
+                    0x20..=0x2F => StripState::Escape,
+                    _ => StripState::Ground,
+                }
+            },
+            StripState::Csi => {
+                match b {
+                    // final byte
+                    0x40..=0x7E => StripState::Ground,
+                    _ => StripState::Csi,
+                }
+            },
+            StripState::String | StripState::StringEscape => {
+                match b {
+                    BEL => StripState::Ground,
+                    b'\\' if self.state == StripState::StringEscape => StripState::Ground,
+                    ESC => StripState::StringEscape,
+                    _ => StripState::String,
+                }
+            },
+        };
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(&[b"plain text\r\n"] => "plain text\n" ; "carriage return")]
+    #[test_case(&[b"\x1b[1;31mred\x1b[m"] => "red" ; "sgr")]
+    #[test_case(&[b"\x1b]0;title\x07prompt$ "] => "prompt$ " ; "osc with bel")]
+    #[test_case(&[b"\x1b]7;file:///tmp\x1b\\ok"] => "ok" ; "osc with st")]
+    #[test_case(&[b"a\x1b[", b"2", b"Kb"] => "ab" ; "csi split between chunks")]
+    #[test_case(&[b"\x1b(Bx\x1b=y"] => "xy" ; "charset and keypad")]
+    #[test_case(&[b"\tz\x07\x08"] => "\tz" ; "control characters")]
+    fn ansi_stripper_test(chunks: &[&[u8]]) -> String {
+        let mut stripper = AnsiStripper::default();
+        let res: Vec<u8> = chunks.iter().flat_map(|c| stripper.strip(c)).collect();
+        String::from_utf8(res).unwrap()
+    }
+}
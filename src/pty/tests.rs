@@ -1,16 +1,49 @@
-use std::{fmt::Write, time::Duration};
+use std::{fmt::Write as _, io::Write as _, time::Duration};
 
 use bollard::container::LogOutput;
 use bytes::Bytes;
+use futures::Stream;
 use indoc::indoc;
+use terminput::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    ScrollDirection,
+};
 use test_case::test_case;
-use tokio::io::AsyncReadExt;
+use tokio::io::{AsyncReadExt, AsyncWrite};
 
-use crate::pty::Pty;
+use crate::pty::{CopyMotion, CursorShape, Pty, SelectionKind, SessionLogFormat, search};
 
 const SCREEN_SIZE_WIDTH: u16 = 10;
 const SCREEN_SIZE_HEIGHT: u16 = 3;
 
+/// Creates a PTY of the size, reading the output from `output` and writing the input into
+/// `input`.
+fn test_pty(
+    output: impl Stream<Item = Result<LogOutput, bollard::errors::Error>> + Send + 'static,
+    input: impl AsyncWrite + Send + 'static,
+    rows: u16,
+    cols: u16,
+) -> Pty {
+    let mut pty = Pty::new(
+        "test_session".to_string(),
+        Box::pin(output),
+        Box::pin(input),
+    );
+    pty.set_size(rows, cols);
+    pty
+}
+
+/// Returns the output stream, yielding the chunks as `stdout`.
+fn output_stream(
+    chunks: &'static [&[u8]]
+) -> impl Stream<Item = Result<LogOutput, bollard::errors::Error>> + Send + 'static {
+    futures::stream::iter(chunks.iter().map(|s| {
+        Ok(LogOutput::StdOut {
+            message: Bytes::copy_from_slice(s),
+        })
+    }))
+}
+
 // -----
 // Control test cases
 // -----
@@ -121,6 +154,24 @@ const SCREEN_SIZE_HEIGHT: u16 = 3;
     ;
     "DECALN V-1: Simple Usage" // <https://ghostty.org/docs/vt/esc/decaln#decaln-v-1:-simple-usage>
 )]
+#[test_case(
+    &[
+        b"\x1B[2;3r",  // scroll region rows 2-3
+        b"\x1B#8",
+        b"\x1B[T",     // scroll down the whole screen, as the scroll region is reset
+    ]
+    =>
+    (
+        indoc!{"
+        |__________|
+        |EEEEEEEEEE|
+        |EEEEEEEEEE|
+        "}.to_string(),
+        (0, 0),
+    )
+    ;
+    "DECALN V-2: Reset Margins" // <https://ghostty.org/docs/vt/esc/decaln#decaln-v-2:-reset-margins>
+)]
 #[test_case(
     &[
         b"\x1B[1;5H",
@@ -296,21 +347,275 @@ const SCREEN_SIZE_HEIGHT: u16 = 3;
     ;
     "CHT V-2: Right From Before a Tabstop" // <https://ghostty.org/docs/vt/csi/cht#cht-v-2:-right-from-before-a-tabstop>
 )]
+#[test_case(
+    &[
+        b"\x1B[1;2H",
+        b"A",
+        b"\x1B[2I",
+        b"X",
+    ]
+    =>
+    (
+        indoc!{"
+        |_A_______X|
+        |__________|
+        |__________|
+        "}.to_string(),
+        (0, 10),
+    )
+    ;
+    "CHT V-3: Right Multiple Tabstops" // <https://ghostty.org/docs/vt/csi/cht>
+)]
+#[test_case(
+    &[
+        b"A",
+        b"\x1B[1;5H",
+        b"B",
+        b"\x1B[3;1H",
+        b"C",
+        b"\x1B[1;2r",  // scroll region rows 1-2
+        b"\x1B[2;1H",
+        b"\x1BD",      // IND - index (scrolls the scroll region only)
+        b"X",
+    ]
+    =>
+    (
+        indoc!{"
+        |__________|
+        |X_________|
+        |C_________|
+        "}.to_string(),
+        (1, 1),
+    )
+    ;
+    "IND V-2: Bottom of Scroll Region" // <https://ghostty.org/docs/vt/esc/ind#ind-v-3:-bottom-of-scroll-region>
+)]
+#[test_case(
+    &[
+        b"A\r\n",
+        b"B\r\n",
+        b"C",
+        b"\x1B[1;2r",  // scroll region rows 1-2
+        b"\x1B[3;1H",
+        b"\x1BD",      // IND - index (outside of the scroll region, no scroll)
+        b"X",
+    ]
+    =>
+    (
+        indoc!{"
+        |A_________|
+        |B_________|
+        |X_________|
+        "}.to_string(),
+        (2, 1),
+    )
+    ;
+    "IND V-3: Bottom of Screen Outside of Scroll Region" // <https://ghostty.org/docs/vt/esc/ind>
+)]
+#[test_case(
+    &[
+        b"A\r\n",
+        b"B\r\n",
+        b"C",
+        b"\x1B[2;3r",  // scroll region rows 2-3
+        b"\x1B[2;1H",
+        b"\x1BM",      // RI - reverse index (scrolls the scroll region only)
+        b"X",
+    ]
+    =>
+    (
+        indoc!{"
+        |A_________|
+        |X_________|
+        |B_________|
+        "}.to_string(),
+        (1, 1),
+    )
+    ;
+    "RI V-3: Top of Scroll Region" // <https://ghostty.org/docs/vt/esc/ri>
+)]
+#[test_case(
+    &[
+        b"\x1B[3g",    // clear all tab stops
+        b"\x1B[1;4H",
+        b"\x1BH",      // HTS - set a tab stop at column 4
+        b"\x1B[1;1H",
+        b"\x09",
+        b"X",
+    ]
+    =>
+    (
+        indoc!{"
+        |___X______|
+        |__________|
+        |__________|
+        "}.to_string(),
+        (0, 4),
+    )
+    ;
+    "HTS V-1: Custom Tab Stop" // <https://ghostty.org/docs/vt/esc/hts>
+)]
+#[test_case(
+    &[
+        b"\x1B[3g",    // clear all tab stops
+        b"\x1B[1;4H",
+        b"\x1BH",      // HTS - set a tab stop at column 4
+        b"\x1B[1;1H",
+        b"\x1B]2;a\tb\x07", // HT inside the OSC payload
+        b"\x09",
+        b"X",
+    ]
+    =>
+    (
+        indoc!{"
+        |___X______|
+        |__________|
+        |__________|
+        "}.to_string(),
+        (0, 4),
+    )
+    ;
+    "HTS: Custom Tab Stop, HT Inside OSC"
+)]
+#[test_case(
+    &[
+        // clear all tab stops, set a tab stop at column 4 (HTS), HT in the same chunk
+        b"\x1B[3g\x1B[1;4H\x1BH\x1B[1;1H\x09X",
+    ]
+    =>
+    (
+        indoc!{"
+        |___X______|
+        |__________|
+        |__________|
+        "}.to_string(),
+        (0, 4),
+    )
+    ;
+    "HTS: Custom Tab Stop, HT In The Same Chunk"
+)]
+#[test_case(
+    &[
+        b"\x1B[1;3H",
+        b"\x1BH",      // HTS - set a tab stop at column 3
+        b"\x1B[1;1H",
+        b"\x09A\x09B",
+    ]
+    =>
+    (
+        indoc!{"
+        |__A_____B_|
+        |__________|
+        |__________|
+        "}.to_string(),
+        (0, 9),
+    )
+    ;
+    "HTS V-2: Tab Stop Added To Defaults" // <https://ghostty.org/docs/vt/esc/hts>
+)]
+#[test_case(
+    &[
+        b"\x1B[1;9H",
+        b"\x1B[g",     // TBC - clear the tab stop at column 9
+        b"\x1B[1;1H",
+        b"\x09",
+        b"X",
+    ]
+    =>
+    (
+        indoc!{"
+        |_________X|
+        |__________|
+        |__________|
+        "}.to_string(),
+        (0, 10),
+    )
+    ;
+    "TBC V-1: Clear Current Tab Stop" // <https://ghostty.org/docs/vt/csi/tbc>
+)]
+#[test_case(
+    &[
+        b"\x1B[3g",    // TBC - clear all tab stops
+        b"\x1B[1;8H",
+        b"\x1B[Z",     // CBT - back to the first column, no tab stops left
+        b"X",
+    ]
+    =>
+    (
+        indoc!{"
+        |X_________|
+        |__________|
+        |__________|
+        "}.to_string(),
+        (0, 1),
+    )
+    ;
+    "TBC V-2: Clear All Tab Stops" // <https://ghostty.org/docs/vt/csi/tbc>
+)]
+#[test_case(
+    &[
+        b"A",
+        b"\x1B[3b",    // REP - repeat 'A' 3 times
+    ]
+    =>
+    (
+        indoc!{"
+        |AAAA______|
+        |__________|
+        |__________|
+        "}.to_string(),
+        (0, 4),
+    )
+    ;
+    "REP V-1: Simple Usage" // <https://ghostty.org/docs/vt/csi/rep#rep-v-1:-simple-usage>
+)]
+#[test_case(
+    &[
+        b"\x1B[1;9H",
+        b"AB",
+        b"\x1B[2b",    // REP - repeat 'B' twice, wrapping to the next line
+    ]
+    =>
+    (
+        indoc!{"
+        |________AB|
+        |BB________|
+        |__________|
+        "}.to_string(),
+        (1, 2),
+    )
+    ;
+    "REP V-2: Soft-Wrap" // <https://ghostty.org/docs/vt/csi/rep#rep-v-2:-soft-wrap>
+)]
+#[test_case(
+    &[
+        b"\x1B[b",     // REP - nothing to repeat
+    ]
+    =>
+    (
+        indoc!{"
+        |__________|
+        |__________|
+        |__________|
+        "}.to_string(),
+        (0, 0),
+    )
+    ;
+    "REP V-3: No Previous Character" // <https://ghostty.org/docs/vt/csi/rep>
+)]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn pty_test(stdout: &'static [&[u8]]) -> (String, (u16, u16)) {
     const TIMEOUT: Duration = Duration::from_secs(1);
 
-    let input = Box::pin(Vec::new());
-    let output = Box::pin(futures::stream::iter(stdout.iter().map(|s| {
-        Ok(LogOutput::StdOut {
-            message: Bytes::copy_from_slice(s),
-        })
-    })));
-    let mut pty = Pty::new("test_session".to_string(), output, input);
-    pty.set_size(SCREEN_SIZE_HEIGHT, SCREEN_SIZE_WIDTH);
+    let mut pty = test_pty(
+        output_stream(stdout),
+        Vec::new(),
+        SCREEN_SIZE_HEIGHT,
+        SCREEN_SIZE_WIDTH,
+    );
 
     for _ in stdout {
-        pty.process_stdout_and_stderr(TIMEOUT);
+        pty.process_stdout_and_stderr(TIMEOUT).await;
     }
 
     let mut res = String::new();
@@ -349,22 +654,578 @@ async fn pty_test(stdout: &'static [&[u8]]) -> (String, (u16, u16)) {
     ;
     "DSR V-2: Cursor Position" // <https://ghostty.org/docs/vt/csi/dsr#dsr-v-2:-cursor-position>
 )]
+#[test_case(
+    &[
+        b"\x1B]52;c;?\x07",
+    ]
+    =>
+    Vec::<u8>::new()
+    ;
+    "OSC 52: Clipboard Query Disallowed By Default"
+)]
+#[test_case(
+    &[
+        b"\x1B[?u",
+    ]
+    =>
+    b"\x1b[?0u".to_vec()
+    ;
+    "Kitty Keyboard: Query Default Flags" // <https://sw.kovidgoyal.net/kitty/keyboard-protocol/#progressive-enhancement>
+)]
+#[test_case(
+    &[
+        b"\x1B[>1u",
+        b"\x1B[>3u",
+        b"\x1B[=4;2u",
+        b"\x1B[?u",
+        b"\x1B[<u",
+        b"\x1B[?u",
+    ]
+    =>
+    b"\x1b[?7u\x1b[?1u".to_vec()
+    ;
+    "Kitty Keyboard: Push, Set And Pop Flags" // <https://sw.kovidgoyal.net/kitty/keyboard-protocol/#progressive-enhancement>
+)]
+#[test_case(
+    &[
+        b"\x1B[>1u",
+        b"\x1B[?1049h",
+        b"\x1B[?u",
+    ]
+    =>
+    b"\x1b[?0u".to_vec()
+    ;
+    "Kitty Keyboard: Alternate Screen Flags" // <https://sw.kovidgoyal.net/kitty/keyboard-protocol/#progressive-enhancement>
+)]
+#[test_case(
+    &[
+        b"\x1B[c",
+    ]
+    =>
+    b"\x1b[?62;22c".to_vec()
+    ;
+    "DA1 V-1: Primary Device Attributes" // <https://ghostty.org/docs/vt/csi/da1>
+)]
+#[test_case(
+    &[
+        b"\x1B[>c",
+    ]
+    =>
+    b"\x1b[>1;10;0c".to_vec()
+    ;
+    "DA2 V-1: Secondary Device Attributes" // <https://ghostty.org/docs/vt/csi/da2>
+)]
+#[test_case(
+    &[
+        b"\x1B[18t",
+        b"\x1B[19t",
+    ]
+    =>
+    b"\x1b[8;3;10t\x1b[9;3;10t".to_vec()
+    ;
+    "XTWINOPS: Report Size In Characters" // <https://invisible-island.net/xterm/ctlseqs/ctlseqs.html>
+)]
+#[test_case(
+    &[
+        b"\x1B[?2004$p",
+        b"\x1B[?2004h",
+        b"\x1B[?2004$p",
+    ]
+    =>
+    b"\x1b[?2004;2$y\x1b[?2004;1$y".to_vec()
+    ;
+    "DECRQM V-1: Bracketed Paste" // <https://ghostty.org/docs/vt/csi/decrqm>
+)]
+#[test_case(
+    &[
+        b"\x1B[?1004h",
+        b"\x1B[?1004$p",
+        b"\x1B[?25l",
+        b"\x1B[?25$p",
+    ]
+    =>
+    b"\x1b[?1004;1$y\x1b[?25;2$y".to_vec()
+    ;
+    "DECRQM V-2: Focus Reporting And Cursor Visibility" // <https://ghostty.org/docs/vt/csi/decrqm>
+)]
+#[test_case(
+    &[
+        b"\x1B[?9999$p",
+        b"\x1B[4$p",
+    ]
+    =>
+    b"\x1b[?9999;0$y\x1b[4;0$y".to_vec()
+    ;
+    "DECRQM V-3: Unknown Modes" // <https://ghostty.org/docs/vt/csi/decrqm>
+)]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn pty_with_response_test(stdout: &'static [&[u8]]) -> Vec<u8> {
     const TIMEOUT: Duration = Duration::from_secs(1);
 
     let (input_writer, mut input_reader) = tokio::io::duplex(1024);
-    let input = Box::pin(input_writer);
-    let output = Box::pin(futures::stream::iter(stdout.iter().map(|s| {
-        Ok(LogOutput::StdOut {
-            message: Bytes::copy_from_slice(s),
-        })
-    })));
-    let mut pty = Pty::new("test_session".to_string(), output, input);
-    pty.set_size(SCREEN_SIZE_HEIGHT, SCREEN_SIZE_WIDTH);
+    let mut pty = test_pty(
+        output_stream(stdout),
+        input_writer,
+        SCREEN_SIZE_HEIGHT,
+        SCREEN_SIZE_WIDTH,
+    );
+
+    for _ in stdout {
+        pty.process_stdout_and_stderr(TIMEOUT).await;
+    }
+
+    drop(pty);
+    // The write side is dropped with pty, so this will read until EOF
+    let mut result = Vec::new();
+    input_reader.read_to_end(&mut result).await.unwrap();
+    result
+}
+
+#[test_case(
+    &[],
+    MouseEventKind::Down(MouseButton::Left)
+    =>
+    (false, b"".to_vec())
+    ;
+    "Mouse: Reporting Disabled"
+)]
+#[test_case(
+    &[b"\x1B[?1000h"],
+    MouseEventKind::Down(MouseButton::Left)
+    =>
+    (true, b"\x1b[M !\"".to_vec())
+    ;
+    "Mouse: VT200 Legacy Encoding"
+)]
+#[test_case(
+    &[b"\x1B[?1000h", b"\x1B[?1006h"],
+    MouseEventKind::Up(MouseButton::Right)
+    =>
+    (true, b"\x1b[<2;1;2m".to_vec())
+    ;
+    "Mouse: VT200 SGR Encoding"
+)]
+#[test_case(
+    &[b"\x1B[?1000h", b"\x1B[?1006h"],
+    MouseEventKind::Drag(MouseButton::Left)
+    =>
+    (true, b"".to_vec())
+    ;
+    "Mouse: VT200 Ignores Motion"
+)]
+#[test_case(
+    &[b"\x1B[?1002h", b"\x1B[?1006h"],
+    MouseEventKind::Drag(MouseButton::Left)
+    =>
+    (true, b"\x1b[<32;1;2M".to_vec())
+    ;
+    "Mouse: Button Motion SGR Encoding"
+)]
+#[test_case(
+    &[b"\x1B[?1000h", b"\x1B[?1006h"],
+    MouseEventKind::Scroll(ScrollDirection::Up)
+    =>
+    (true, b"\x1b[<64;1;2M".to_vec())
+    ;
+    "Mouse: Wheel SGR Encoding"
+)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pty_mouse_test(
+    stdout: &'static [&[u8]],
+    kind: MouseEventKind,
+) -> (bool, Vec<u8>) {
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    let (input_writer, mut input_reader) = tokio::io::duplex(1024);
+    let mut pty = test_pty(
+        output_stream(stdout),
+        input_writer,
+        SCREEN_SIZE_HEIGHT,
+        SCREEN_SIZE_WIDTH,
+    );
+
+    for _ in stdout {
+        pty.process_stdout_and_stderr(TIMEOUT).await;
+    }
+
+    let reported = pty.process_mouse_event(MouseEvent {
+        kind,
+        column: 0,
+        row: 1,
+        modifiers: KeyModifiers::NONE,
+    });
+
+    drop(pty);
+    // The write side is dropped with pty, so this will read until EOF
+    let mut result = Vec::new();
+    input_reader.read_to_end(&mut result).await.unwrap();
+    (reported, result)
+}
+
+#[test_case(
+    &[b"\x1B]2;vim main.rs\x07"]
+    =>
+    (Some("vim main.rs".to_string()), None)
+    ;
+    "OSC 2: Set Window Title"
+)]
+#[test_case(
+    &[b"\x1B]0;htop\x07", b"\x1B]0;\x07"]
+    =>
+    (None, None)
+    ;
+    "OSC 0: Reset Window Title"
+)]
+#[test_case(
+    &[b"\x1B]7;file://host/home/user/my%20project\x1B\\"]
+    =>
+    (None, Some("/home/user/my project".to_string()))
+    ;
+    "OSC 7: Report Working Directory"
+)]
+#[test_case(
+    &[b"\x1B]7;file:///tmp/a;b\x07"]
+    =>
+    (None, Some("/tmp/a;b".to_string()))
+    ;
+    "OSC 7: Report Working Directory Without Hostname"
+)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pty_title_test(stdout: &'static [&[u8]]) -> (Option<String>, Option<String>) {
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    let mut pty = test_pty(
+        output_stream(stdout),
+        Vec::new(),
+        SCREEN_SIZE_HEIGHT,
+        SCREEN_SIZE_WIDTH,
+    );
+
+    for _ in stdout {
+        pty.process_stdout_and_stderr(TIMEOUT).await;
+    }
+
+    (pty.title().map(String::from), pty.cwd().map(String::from))
+}
+
+#[test_case(
+    "error", false, 0
+    =>
+    (1, vec![(1, 0, 5, true)])
+    ;
+    "Search: Forward Wraps Around To Scrollback"
+)]
+#[test_case(
+    "[a-z]\\d", true, 0
+    =>
+    (2, vec![(1, 0, 2, true)])
+    ;
+    "Search: Backward From Bottom Of Screen"
+)]
+#[test_case(
+    "[a-z]\\d", true, 1
+    =>
+    (2, vec![(0, 0, 2, true)])
+    ;
+    "Search: Next Match Backward"
+)]
+#[test_case(
+    "界", false, 0
+    =>
+    (1, vec![(2, 2, 4, true)])
+    ;
+    "Search: Wide Characters"
+)]
+#[test_case(
+    "missing", false, 0
+    =>
+    (0, vec![])
+    ;
+    "Search: Not Found"
+)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pty_search_test(
+    pattern: &str,
+    backward: bool,
+    next: usize,
+) -> (usize, Vec<(u16, u16, u16, bool)>) {
+    const TIMEOUT: Duration = Duration::from_secs(1);
+    const STDOUT: &[u8] = "a1\r\nb\r\nerror x\r\nc\r\nd2\r\n世界".as_bytes();
+
+    let mut pty = test_pty(
+        output_stream(&[STDOUT]),
+        Vec::new(),
+        SCREEN_SIZE_HEIGHT,
+        SCREEN_SIZE_WIDTH,
+    );
+    pty.process_stdout_and_stderr(TIMEOUT).await;
+
+    let found = pty.search(pattern, backward).unwrap();
+    for _ in 0..next {
+        pty.search_next(false);
+    }
+    let visible = pty
+        .visible_search_matches()
+        .into_iter()
+        .map(|m| (m.row, m.start, m.end, m.current))
+        .collect();
+    (found, visible)
+}
+
+#[test_case(
+    &[CopyMotion::Up, CopyMotion::Up, CopyMotion::Up, CopyMotion::LineStart],
+    SelectionKind::Char,
+    &[CopyMotion::Right, CopyMotion::Right, CopyMotion::Right, CopyMotion::Right]
+    =>
+    Some("error".to_string())
+    ;
+    "Copy Mode: Char Selection In Scrollback"
+)]
+#[test_case(
+    &[CopyMotion::Up, CopyMotion::Up, CopyMotion::Up],
+    SelectionKind::Line,
+    &[CopyMotion::Down]
+    =>
+    Some("error x\nc".to_string())
+    ;
+    "Copy Mode: Line Selection"
+)]
+#[test_case(
+    &[CopyMotion::Up, CopyMotion::Up, CopyMotion::LineEnd],
+    SelectionKind::Char,
+    &[CopyMotion::Up, CopyMotion::Left, CopyMotion::Left, CopyMotion::Left]
+    =>
+    Some("x\nc".to_string())
+    ;
+    "Copy Mode: Backward Char Selection Across Lines"
+)]
+#[test_case(
+    &[CopyMotion::Top],
+    SelectionKind::Char,
+    &[CopyMotion::Right]
+    =>
+    Some("a1".to_string())
+    ;
+    "Copy Mode: Top Of Scrollback"
+)]
+#[test_case(
+    &[CopyMotion::LineStart],
+    SelectionKind::Char,
+    &[CopyMotion::Right, CopyMotion::Right, CopyMotion::Right]
+    =>
+    Some("世界".to_string())
+    ;
+    "Copy Mode: Wide Characters"
+)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pty_copy_mode_test(
+    before: &[CopyMotion],
+    kind: SelectionKind,
+    after: &[CopyMotion],
+) -> Option<String> {
+    const TIMEOUT: Duration = Duration::from_secs(1);
+    const STDOUT: &[u8] = "a1\r\nb\r\nerror x\r\nc\r\nd2\r\n世界".as_bytes();
+
+    let mut pty = test_pty(
+        output_stream(&[STDOUT]),
+        Vec::new(),
+        SCREEN_SIZE_HEIGHT,
+        SCREEN_SIZE_WIDTH,
+    );
+    pty.process_stdout_and_stderr(TIMEOUT).await;
+
+    pty.enter_copy_mode();
+    for motion in before {
+        pty.copy_mode_move(*motion);
+    }
+    pty.copy_mode_toggle_selection(kind);
+    for motion in after {
+        pty.copy_mode_move(*motion);
+    }
+    pty.copy_mode_selected_text()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pty_copy_mode_freezes_view_test() {
+    const TIMEOUT: Duration = Duration::from_millis(100);
+
+    let (output_tx, output_rx) = futures::channel::mpsc::unbounded();
+    let mut pty = test_pty(output_rx, Vec::new(), SCREEN_SIZE_HEIGHT, SCREEN_SIZE_WIDTH);
+    let send = |s: &'static [u8]| {
+        output_tx
+            .unbounded_send(Ok(LogOutput::StdOut {
+                message: Bytes::from_static(s),
+            }))
+            .unwrap();
+    };
+    send(b"a\r\nb");
+    pty.process_stdout_and_stderr(TIMEOUT).await;
+
+    pty.enter_copy_mode();
+    pty.copy_mode_move(CopyMotion::Up);
+    pty.copy_mode_toggle_selection(SelectionKind::Line);
+    send(b"\r\nc\r\nd\r\ne\r\nf\r\ng");
+    drop(output_tx);
+    // The output is processed, but the copy mode keeps showing the snapshot
+    assert!(!pty.process_stdout_and_stderr(TIMEOUT).await);
+    assert!(!pty.process_stdout_and_stderr(TIMEOUT).await);
+    assert!(pty.is_exited());
+    assert_eq!(pty.screen().contents(), "a\nb");
+    assert_eq!(pty.copy_mode_selected_text(), Some("a".to_string()));
+
+    pty.exit_copy_mode();
+    assert!(pty.screen().contents().ends_with('g'));
+}
+
+#[test_case(
+    SessionLogFormat::Plain
+    =>
+    "a1\nb\nerror x\nc\nd2\n世界next line\n".to_string()
+    ;
+    "Session Log: Plain"
+)]
+#[test_case(
+    SessionLogFormat::Formatted
+    =>
+    "a1\x1b[m\nb\x1b[m\nerror x\x1b[m\nc\x1b[m\nd2\x1b[m\n世界\x1b[m\nnext \x1b[1mline\r\n".to_string()
+    ;
+    "Session Log: Formatted"
+)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pty_session_log_test(format: SessionLogFormat) -> String {
+    const TIMEOUT: Duration = Duration::from_secs(1);
+    const BEFORE_LOG: &[u8] = "a1\r\nb\r\nerror x\r\nc\r\nd2\r\n世界".as_bytes();
+    const AFTER_LOG: &[u8] = b"next \x1b[1mline\r\n";
+
+    let send = |tx: &futures::channel::mpsc::UnboundedSender<_>, s: &'static [u8]| {
+        tx.unbounded_send(Ok(LogOutput::StdOut {
+            message: Bytes::from_static(s),
+        }))
+        .unwrap();
+    };
+    let (output_tx, output_rx) = futures::channel::mpsc::unbounded();
+    let mut pty = test_pty(output_rx, Vec::new(), SCREEN_SIZE_HEIGHT, SCREEN_SIZE_WIDTH);
+    send(&output_tx, BEFORE_LOG);
+    pty.process_stdout_and_stderr(TIMEOUT).await;
+
+    let path = std::env::temp_dir().join(format!("scell_{}.log", uuid::Uuid::now_v7()));
+    pty.log_session(&path, format).unwrap();
+    send(&output_tx, AFTER_LOG);
+    pty.process_stdout_and_stderr(TIMEOUT).await;
+    drop(pty);
+
+    let res = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    res
+}
+
+#[test_case(
+    &[],
+    Event::Paste("ls\n".to_string())
+    =>
+    b"ls\n".to_vec()
+    ;
+    "Paste: Bracketed Paste Disabled"
+)]
+#[test_case(
+    &[b"\x1B[?2004h"],
+    Event::Paste("ls\x1b[201~\n".to_string())
+    =>
+    b"\x1b[200~ls\n\x1b[201~".to_vec()
+    ;
+    "Paste: Bracketed Paste Enabled" // <https://ghostty.org/docs/vt/reference#modes>
+)]
+#[test_case(
+    &[],
+    Event::FocusGained
+    =>
+    b"".to_vec()
+    ;
+    "Focus: Reporting Disabled"
+)]
+#[test_case(
+    &[b"\x1B[?1004h"],
+    Event::FocusLost
+    =>
+    b"\x1b[O".to_vec()
+    ;
+    "Focus: Reporting Enabled" // <https://ghostty.org/docs/vt/reference#modes>
+)]
+#[test_case(
+    &[b"\x1B[?1004h", b"\x1B[?1004l"],
+    Event::FocusGained
+    =>
+    b"".to_vec()
+    ;
+    "Focus: Reporting Disabled Again"
+)]
+#[test_case(
+    &[],
+    Event::Key(KeyEvent::new(KeyCode::Esc))
+    =>
+    b"\x1b".to_vec()
+    ;
+    "Key: Legacy Encoding"
+)]
+#[test_case(
+    &[],
+    Event::Key(KeyEvent::new(KeyCode::Char('a')).kind(KeyEventKind::Release))
+    =>
+    b"".to_vec()
+    ;
+    "Key: Legacy Encoding Drops Release"
+)]
+#[test_case(
+    &[b"\x1B[>1u"],
+    Event::Key(KeyEvent::new(KeyCode::Esc))
+    =>
+    b"\x1b[27u".to_vec()
+    ;
+    "Key: Kitty Disambiguate Escape Codes" // <https://sw.kovidgoyal.net/kitty/keyboard-protocol/#disambiguate-escape-codes>
+)]
+#[test_case(
+    &[b"\x1B[>3u"],
+    Event::Key(KeyEvent::new(KeyCode::Char('a')).kind(KeyEventKind::Release))
+    =>
+    b"\x1b[97;1:3u".to_vec()
+    ;
+    "Key: Kitty Report Event Types" // <https://sw.kovidgoyal.net/kitty/keyboard-protocol/#report-event-types>
+)]
+#[test_case(
+    &[b"\x1B[>1u", b"\x1B[<1u"],
+    Event::Key(KeyEvent::new(KeyCode::Esc))
+    =>
+    b"\x1b".to_vec()
+    ;
+    "Key: Kitty Flags Popped"
+)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pty_input_test(
+    stdout: &'static [&[u8]],
+    event: Event,
+) -> Vec<u8> {
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    let (input_writer, mut input_reader) = tokio::io::duplex(1024);
+    let mut pty = test_pty(
+        output_stream(stdout),
+        input_writer,
+        SCREEN_SIZE_HEIGHT,
+        SCREEN_SIZE_WIDTH,
+    );
 
     for _ in stdout {
-        pty.process_stdout_and_stderr(TIMEOUT);
+        pty.process_stdout_and_stderr(TIMEOUT).await;
+    }
+
+    match event {
+        Event::Key(key) => {
+            pty.process_key_event(key);
+        },
+        Event::Paste(text) => pty.process_paste(&text),
+        Event::FocusGained => pty.process_focus(true),
+        Event::FocusLost => pty.process_focus(false),
+        _ => {},
     }
 
     drop(pty);
@@ -373,3 +1234,120 @@ async fn pty_with_response_test(stdout: &'static [&[u8]]) -> Vec<u8> {
     input_reader.read_to_end(&mut result).await.unwrap();
     result
 }
+
+#[test_case(&[] => CursorShape::Block ; "DECSCUSR: Default")]
+#[test_case(&[b"\x1B[4 q"] => CursorShape::Underline ; "DECSCUSR: Steady Underline")] // <https://ghostty.org/docs/vt/csi/decscusr>
+#[test_case(&[b"\x1B[5 q"] => CursorShape::Bar ; "DECSCUSR: Blinking Bar")] // <https://ghostty.org/docs/vt/csi/decscusr>
+#[test_case(&[b"\x1B[6 q", b"\x1B[0 q"] => CursorShape::Block ; "DECSCUSR: Reset To Default")] // <https://ghostty.org/docs/vt/csi/decscusr>
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pty_cursor_shape_test(stdout: &'static [&[u8]]) -> CursorShape {
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    let mut pty = test_pty(
+        output_stream(stdout),
+        Vec::new(),
+        SCREEN_SIZE_HEIGHT,
+        SCREEN_SIZE_WIDTH,
+    );
+
+    for _ in stdout {
+        pty.process_stdout_and_stderr(TIMEOUT).await;
+    }
+    pty.cursor_shape()
+}
+
+#[test_case(&[b"1\r\n2"] => 0 ; "no scrollback")]
+#[test_case(&[b"1\r\n2\r\n3\r\n4", b"\r\n5"] => 2 ; "scrolled lines")]
+#[test_case(&[b"1\r\n2\r\n3\r\n4\r\n5", b"\x1B[5S"] => 5 ; "scroll up")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pty_scrollback_len_test(stdout: &'static [&[u8]]) -> usize {
+    const TIMEOUT: Duration = Duration::from_secs(1);
+
+    let mut pty = test_pty(
+        output_stream(stdout),
+        Vec::new(),
+        SCREEN_SIZE_HEIGHT,
+        SCREEN_SIZE_WIDTH,
+    );
+
+    for _ in stdout {
+        pty.process_stdout_and_stderr(TIMEOUT).await;
+    }
+    // The scrollback position is kept
+    pty.scroll_up(1);
+    let scrollback = pty.screen().scrollback();
+    assert_eq!(
+        search::scrollback_len(pty.parser.screen_mut()),
+        pty.scrollback_len
+    );
+    assert_eq!(pty.screen().scrollback(), scrollback);
+    pty.scrollback_len
+}
+
+/// Processes a `vim`-like output on a large screen: a scroll region above the status
+/// line, with lots of cursor, tab and scroll callbacks.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pty_callbacks_benchmark_test() {
+    const TIMEOUT: Duration = Duration::from_secs(1);
+    const ROWS: u16 = 50;
+    const COLS: u16 = 200;
+    const LINES: usize = 10_000;
+
+    let mut stdout = Vec::new();
+    // header, status line and the scroll region between them
+    write!(stdout, "HEADER\x1B[{ROWS};1HSTATUS\x1B[2;{}r", ROWS - 1).unwrap();
+    for i in 0..LINES {
+        // print a line at the bottom of the scroll region and scroll it up with IND
+        write!(stdout, "\x1B[{};1Hline {i}\x1BD", ROWS - 1).unwrap();
+        // move around with CHT/CBT and draw with REP
+        write!(stdout, "\x1B[3I\x1B[2Z~\x1B[5b").unwrap();
+    }
+
+    let output = futures::stream::once(async move {
+        Ok(LogOutput::StdOut {
+            message: Bytes::from(stdout),
+        })
+    });
+    let mut pty = test_pty(output, Vec::new(), ROWS, COLS);
+
+    pty.process_stdout_and_stderr(TIMEOUT).await;
+
+    let rows: Vec<_> = pty.screen().rows(0, COLS).collect();
+    assert_eq!(rows.first().unwrap(), "HEADER");
+    assert_eq!(rows.last().unwrap(), "STATUS");
+    assert_eq!(
+        &rows[usize::from(ROWS) - 3],
+        &format!("{:<16}~~~~~~", format!("line {}", LINES - 1))
+    );
+    assert_eq!(&rows[usize::from(ROWS) - 2], &format!("{:<16}~~~~~~", ""));
+}
+
+/// All pending output chunks are processed at once, not one per frame, and the end of
+/// the output is detected.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pty_output_drain_test() {
+    const TIMEOUT: Duration = Duration::from_millis(100);
+    const STDOUT: &[&[u8]] = &[b"a", b"b", b"c"];
+
+    let (output_tx, output_rx) = futures::channel::mpsc::unbounded();
+    let mut pty = test_pty(output_rx, Vec::new(), SCREEN_SIZE_HEIGHT, SCREEN_SIZE_WIDTH);
+    for s in STDOUT {
+        output_tx
+            .unbounded_send(Ok(LogOutput::StdOut {
+                message: Bytes::from_static(s),
+            }))
+            .unwrap();
+    }
+    // Let the output be forwarded to the PTY
+    tokio::time::sleep(TIMEOUT).await;
+
+    assert!(pty.process_stdout_and_stderr(TIMEOUT).await);
+    assert_eq!(pty.screen().contents(), "abc");
+    assert!(!pty.process_stdout_and_stderr(TIMEOUT).await);
+    assert!(!pty.is_exited());
+
+    // The output stream ends, once the process inside the terminal exits
+    drop(output_tx);
+    assert!(!pty.process_stdout_and_stderr(TIMEOUT).await);
+    assert!(pty.is_exited());
+}
@@ -0,0 +1,21 @@
+use config::Config;
+use serde::Deserialize;
+
+const ENV_VAR_PREFIX: &str = "SHELL_CELL_TITLE";
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TitleConfig {
+    /// Forward the window title, set inside the container, to the host terminal.
+    #[serde(default)]
+    pub forward: bool,
+}
+
+impl TitleConfig {
+    pub fn init() -> color_eyre::Result<Self> {
+        let res: TitleConfig = Config::builder()
+            .add_source(config::Environment::with_prefix(ENV_VAR_PREFIX).try_parsing(true))
+            .build()?
+            .try_deserialize()?;
+        Ok(res)
+    }
+}
@@ -0,0 +1,57 @@
+//! Forwarding of the window title, set inside the container, to the host terminal.
+
+mod conf;
+
+use std::{
+    io::Write as _,
+    sync::{
+        OnceLock,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use self::conf::TitleConfig;
+
+static TITLE: OnceLock<TitleConfig> = OnceLock::new();
+/// Whether the host terminal title was saved before the first forwarding.
+static TITLE_SAVED: AtomicBool = AtomicBool::new(false);
+
+pub struct Title;
+
+impl Title {
+    pub fn init() -> color_eyre::Result<()> {
+        let config = TitleConfig::init()?;
+        TITLE
+            .set(config)
+            .map_err(|_| color_eyre::eyre::eyre!("Title already initialised"))?;
+        Ok(())
+    }
+
+    /// Sets the host terminal title, if forwarding is enabled.
+    /// The original host title is pushed on the terminal's title stack first, so it could
+    /// be restored with [`Title::restore`].
+    pub fn forward(title: &str) {
+        if !TITLE.get().is_some_and(|c| c.forward) {
+            return;
+        }
+        let mut stdout = std::io::stdout().lock();
+        if !TITLE_SAVED.swap(true, Ordering::Relaxed) {
+            // XTWINOPS - push window title on the stack
+            drop(stdout.write_all(b"\x1b[22;2t"));
+        }
+        // Control characters could break the sequence
+        let title: String = title.chars().filter(|c| !c.is_control()).collect();
+        drop(stdout.write_all(format!("\x1b]2;{title}\x07").as_bytes()));
+        drop(stdout.flush());
+    }
+
+    /// Restores the host terminal title, saved before the first forwarding.
+    pub fn restore() {
+        if TITLE_SAVED.swap(false, Ordering::Relaxed) {
+            let mut stdout = std::io::stdout().lock();
+            // XTWINOPS - pop window title from the stack
+            drop(stdout.write_all(b"\x1b[23;2t"));
+            drop(stdout.flush());
+        }
+    }
+}
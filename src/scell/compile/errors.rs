@@ -53,3 +53,7 @@ pub struct CopySrcNotFound(pub PathBuf, pub TargetName, pub PathBuf);
     "Dockerfile does not exist at {0} while processing 'from_docker' statement for target '{1}' at '{2}'"
 )]
 pub struct DockerfileNotFound(pub PathBuf, pub TargetName, pub PathBuf);
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error("Target '{0}' of the Shell-Cell file at '{1}' is invalid: {2}")]
+pub struct InvalidTarget(pub TargetName, pub PathBuf, pub String);
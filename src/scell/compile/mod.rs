@@ -15,9 +15,10 @@ use crate::{
         Link, SCell, SCellContainer,
         compile::errors::{
             CircularTargets, CopySrcNotFound, DirNotFoundFromStmt, DockerfileNotFound,
-            FileLoadFromStmt, MissingEntrypoint, MissingHangStmt, MissingShellStmt, MissingTarget,
-            MountHostDirNotFound,
+            FileLoadFromStmt, InvalidTarget, MissingEntrypoint, MissingHangStmt, MissingShellStmt,
+            MissingTarget, MountHostDirNotFound,
         },
+        graph::StmtOrigins,
         image::SCellImage,
         link::RootNode,
         service::Service,
@@ -47,6 +48,7 @@ type CompiledTarget = (
     Option<HangStmt>,
     Option<ConfigStmt>,
     Vec<(ServiceName, Service)>,
+    StmtOrigins,
 );
 
 impl SCell {
@@ -57,16 +59,16 @@ impl SCell {
         entry: Option<TargetName>,
     ) -> color_eyre::Result<Self> {
         let scell_extra_args = SCellExtraArguments::from_path(&path)?;
-        let mut scell_f = SCellFile::from_path(path, &scell_extra_args)?;
-        let entry_point_target = entry.map_or_else(
-            || {
-                SCELL_DEFAULT_ENTRY_POINT.parse().context(format!(
-                    "'{SCELL_DEFAULT_ENTRY_POINT}' must be a valid Shell-Cell name"
-                ))
-            },
-            Ok,
-        )?;
+        let scell_f = SCellFile::from_path(path, &scell_extra_args)?;
+        let entry_point_target = entry.map_or_else(default_entry_point, Ok)?;
+        Self::compile_file(scell_f, entry_point_target)
+    }
 
+    /// Compiles the `entry_point_target` target of the already loaded Shell-Cell file.
+    fn compile_file(
+        mut scell_f: SCellFile,
+        entry_point_target: TargetName,
+    ) -> color_eyre::Result<Self> {
         let entry_point =
             scell_f
                 .targets
@@ -76,7 +78,7 @@ impl SCell {
                     entry_point_target.clone(),
                 ))?;
 
-        let (links, shell, hang, config, services) =
+        let (links, shell, hang, config, services, origins) =
             compile_target(scell_f, entry_point, entry_point_target)?;
 
         let mut report = Report::new();
@@ -100,10 +102,50 @@ impl SCell {
             container,
             shell: shell.context("'shell' cannot be 'None'")?,
             services,
+            origins,
         })
     }
 }
 
+impl SCell {
+    /// Compiles the `entry` target or, if `all_targets` is set, every target of the
+    /// Shell-Cell file, collecting the errors of all invalid targets into one report.
+    /// The Shell-Cell file is loaded once, so its errors are reported as is, not for
+    /// each target. Returns the checked targets.
+    pub fn check<P: AsRef<Path>>(
+        path: P,
+        entry: Option<TargetName>,
+        all_targets: bool,
+    ) -> color_eyre::Result<Vec<TargetName>> {
+        let scell_extra_args = SCellExtraArguments::from_path(&path)?;
+        let scell_f = SCellFile::from_path(&path, &scell_extra_args)?;
+        let targets = if all_targets {
+            scell_f.targets.keys().cloned().collect()
+        } else {
+            vec![entry.map_or_else(default_entry_point, Ok)?]
+        };
+
+        let mut report = Report::new();
+        for target in &targets {
+            if let Err(e) = Self::compile_file(scell_f.clone(), target.clone()) {
+                report.add_error(UserError::wrap(InvalidTarget(
+                    target.clone(),
+                    scell_f.location.clone(),
+                    format!("{e:#}"),
+                )));
+            }
+        }
+        report.check()?;
+        Ok(targets)
+    }
+}
+
+fn default_entry_point() -> color_eyre::Result<TargetName> {
+    SCELL_DEFAULT_ENTRY_POINT.parse().context(format!(
+        "'{SCELL_DEFAULT_ENTRY_POINT}' must be a valid Shell-Cell name"
+    ))
+}
+
 fn compile_target(
     mut walk_f: SCellFile,
     mut walk_target: TargetStmt,
@@ -115,20 +157,25 @@ fn compile_target(
     let mut shell = None;
     let mut hang = None;
     let mut config = None;
+    let mut origins = StmtOrigins::default();
 
     // TODO: avoid recursion
     let services = resolve_services(walk_target.services, &walk_f)?;
 
     loop {
         // Use only the most recent 'shell` and 'hang' statements from the targets chain.
-        if shell.is_none() {
+        // The link of the current target is the next one pushed to the chain.
+        if shell.is_none() && walk_target.shell.is_some() {
             shell = walk_target.shell;
+            origins.shell = Some(links.len());
         }
-        if hang.is_none() {
+        if hang.is_none() && walk_target.hang.is_some() {
             hang = walk_target.hang;
+            origins.hang = Some(links.len());
         }
-        if config.is_none() {
+        if config.is_none() && walk_target.config.is_some() {
             config = resolve_config(&walk_f.location, &walk_target_name, walk_target.config)?;
+            origins.config = Some(links.len());
         }
         let copy = resolve_copy(
             &walk_f.location,
@@ -189,7 +236,7 @@ fn compile_target(
         }
     }
 
-    Ok((links, shell, hang, config, services))
+    Ok((links, shell, hang, config, services, origins))
 }
 
 fn resolve_config(
@@ -225,15 +272,29 @@ fn resolve_services(
 ) -> color_eyre::Result<Vec<(ServiceName, Service)>> {
     let mut res = Vec::new();
     for (s_name, s) in services.0 {
-        let (links, _shell, hang, config, services) = compile_target(f.clone(), s, s_name.clone())?;
+        let (links, _shell, hang, config, services, origins) =
+            compile_target(f.clone(), s, s_name.clone())?;
         color_eyre::eyre::ensure!(
             links.len() >= 2,
             "It must be at least two links in the target chain"
         );
         let image = SCellImage::new(links, hang.ok_or(MissingHangStmt).mark_as_user_err()?)?;
         let container = SCellContainer::new(config);
-        res.push((s_name, Service { image, container }));
-        res.extend(services);
+        res.push((s_name.clone(), Service {
+            image,
+            container,
+            // The service's shell is never used
+            origins: StmtOrigins {
+                shell: None,
+                ..origins
+            },
+            parent: None,
+        }));
+        // The nested services are flattened, keeping the service they are declared in
+        res.extend(services.into_iter().map(|(name, mut service)| {
+            service.parent.get_or_insert_with(|| s_name.clone());
+            (name, service)
+        }));
     }
     Ok(res)
 }
@@ -0,0 +1,16 @@
+main: {
+	from_image: "from"
+	shell:      "shell"
+	hang:       "hang"
+}
+
+missing_from: {
+	from:  "+missing"
+	shell: "shell"
+	hang:  "hang"
+}
+
+no_shell: {
+	from_image: "from"
+	hang:       "hang"
+}
@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use test_case::test_case;
+
+use crate::scell::{SCell, types::name::TargetName};
+
+const CHECK_FIXTURES: &str = "src/scell/compile/tests/check";
+
+#[test_case(
+    "invalid_targets", None
+    => vec!["main".parse::<TargetName>().unwrap()]
+    ; "default target"
+)]
+#[test_case(
+    "../ok/few_targets", Some("main".parse().unwrap())
+    => vec!["main".parse::<TargetName>().unwrap()]
+    ; "explicit target"
+)]
+fn check_ok_test(
+    dir_path: &str,
+    target: Option<TargetName>,
+) -> Vec<TargetName> {
+    SCell::check(Path::new(CHECK_FIXTURES).join(dir_path), target, false).unwrap()
+}
+
+#[test]
+fn check_all_targets_test() {
+    let err = SCell::check(
+        Path::new(CHECK_FIXTURES).join("invalid_targets"),
+        None,
+        true,
+    )
+    .expect_err("Must fail");
+    let messages: Vec<_> = err.chain().map(ToString::to_string).collect();
+    assert_eq!(messages.len(), 2, "{messages:?}");
+    let location = std::fs::canonicalize(Path::new(CHECK_FIXTURES).join("invalid_targets"))
+        .unwrap()
+        .display()
+        .to_string();
+    for target in ["missing_from", "no_shell"] {
+        assert!(
+            messages.iter().any(|m| {
+                m.starts_with(&format!(
+                    "Target '{target}' of the Shell-Cell file at '{location}'"
+                ))
+            }),
+            "{messages:?}"
+        );
+    }
+}
+
+#[test_case(false ; "entry point")]
+#[test_case(true ; "all targets")]
+fn check_file_error_test(all_targets: bool) {
+    let err = SCell::check(
+        Path::new(CHECK_FIXTURES).join("not_existing"),
+        None,
+        all_targets,
+    )
+    .expect_err("Must fail");
+    let messages: Vec<_> = err.chain().map(ToString::to_string).collect();
+    assert!(
+        messages
+            .first()
+            .is_some_and(|m| m.starts_with("Cannot resolve 'Shell-Cell' file path")),
+        "{messages:?}"
+    );
+    assert!(
+        !messages.iter().any(|m| m.starts_with("Target")),
+        "{messages:?}"
+    );
+}
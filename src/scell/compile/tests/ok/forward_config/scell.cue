@@ -0,0 +1,12 @@
+main: {
+	from_image: "from"
+	shell:      "shell"
+	hang:       "hang"
+	config: {
+		forward: [
+			"ssh-agent",
+			"gitconfig",
+			"known_hosts",
+		]
+	}
+}
@@ -48,10 +48,18 @@ use crate::scell::{SCell, types::name::TargetName};
     "mounts_config", None
     ; "mounts config"
 )]
+#[test_case(
+    "forward_config", None
+    ; "forward config"
+)]
 #[test_case(
     "from_docker", None
     ; "from docker"
 )]
+#[test_case(
+    "services_target", Some("services".parse().unwrap())
+    ; "services target"
+)]
 // TODO: enable this test when service feature would be ready
 // #[test_case(
 //     "services_config", None
@@ -0,0 +1,5 @@
+services: {
+	from_image: "from"
+	shell:      "shell"
+	hang:       "hang"
+}
@@ -1,6 +1,8 @@
 use std::hash::Hash;
 
-use crate::scell::types::target::config::{ConfigStmt, mounts::MountsStmt, ports::PortsStmt};
+use crate::scell::types::target::config::{
+    ConfigStmt, forward::ForwardStmt, mounts::MountsStmt, ports::PortsStmt,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize)]
 pub struct SCellContainer {
@@ -36,4 +38,11 @@ impl SCellContainer {
             .map(|c| c.ports.clone())
             .unwrap_or_default()
     }
+
+    pub fn forward(&self) -> ForwardStmt {
+        self.config
+            .as_ref()
+            .map(|c| c.forward.clone())
+            .unwrap_or_default()
+    }
 }
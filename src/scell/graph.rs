@@ -0,0 +1,149 @@
+//! Dependency graph of the compiled Shell-Cell, showing how its targets are resolved
+//! across the blueprint files.
+
+use std::path::PathBuf;
+
+use crate::scell::{
+    SCell,
+    link::{Link, RootNode},
+    types::{name::TargetName, target::services::ServiceName},
+};
+
+/// Indices of the links in the chain, the effective `shell`, `hang` and `config`
+/// statements are taken from.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StmtOrigins {
+    pub shell: Option<usize>,
+    pub hang: Option<usize>,
+    pub config: Option<usize>,
+}
+
+impl StmtOrigins {
+    /// Returns the names of the statements, taken from the link with the index.
+    fn provided_by(
+        self,
+        i: usize,
+    ) -> Vec<&'static str> {
+        [
+            ("shell", self.shell),
+            ("hang", self.hang),
+            ("config", self.config),
+        ]
+        .into_iter()
+        .filter(|(_, origin)| *origin == Some(i))
+        .map(|(name, _)| name)
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphNode {
+    /// Blueprint target with the resolved location of its blueprint directory and the
+    /// effective statements, it provides.
+    Target {
+        name: TargetName,
+        location: PathBuf,
+        provides: Vec<&'static str>,
+    },
+    /// Root Docker image.
+    Image(String),
+    /// Root Dockerfile.
+    Dockerfile(PathBuf),
+}
+
+/// The `from` chains of the Shell-Cell and its services, each one starts with the
+/// entry point target and ends with the root node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SCellGraph {
+    pub chain: Vec<GraphNode>,
+    /// The services, each one follows the service it is declared in.
+    pub services: Vec<ServiceGraph>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceGraph {
+    pub name: ServiceName,
+    /// The service, this one is declared in, `None` for the services of the Shell-Cell
+    /// target itself.
+    pub parent: Option<ServiceName>,
+    pub chain: Vec<GraphNode>,
+}
+
+impl SCell {
+    pub fn graph(&self) -> SCellGraph {
+        SCellGraph {
+            chain: chain_nodes(self.image.chain(), self.origins),
+            services: self
+                .services
+                .iter()
+                .map(|(name, service)| {
+                    ServiceGraph {
+                        name: name.clone(),
+                        parent: service.parent.clone(),
+                        chain: chain_nodes(service.image.chain(), service.origins),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+fn chain_nodes(
+    chain: &[Link],
+    origins: StmtOrigins,
+) -> Vec<GraphNode> {
+    chain
+        .iter()
+        .enumerate()
+        .map(|(i, link)| {
+            match link {
+                Link::Node { name, location, .. } => {
+                    GraphNode::Target {
+                        name: name.clone(),
+                        location: location.clone(),
+                        provides: origins.provided_by(i),
+                    }
+                },
+                Link::Root(RootNode::Image(image)) => GraphNode::Image(image.to_string()),
+                Link::Root(RootNode::Dockerfile(path)) => GraphNode::Dockerfile(path.clone()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn graph_test() {
+        let path = Path::new("src/scell/compile/tests/ok/ref_other_files");
+        let graph = SCell::compile(path, None).unwrap().graph();
+
+        let other_location =
+            std::fs::canonicalize("src/scell/compile/tests/ok/few_targets").unwrap();
+        assert_eq!(graph, SCellGraph {
+            chain: vec![
+                GraphNode::Target {
+                    name: "main".parse().unwrap(),
+                    location: std::fs::canonicalize(path).unwrap(),
+                    provides: vec!["shell", "hang"],
+                },
+                GraphNode::Target {
+                    name: "other".parse().unwrap(),
+                    location: std::fs::canonicalize(path.join("other")).unwrap(),
+                    provides: Vec::new(),
+                },
+                GraphNode::Target {
+                    name: "other".parse().unwrap(),
+                    location: other_location,
+                    provides: Vec::new(),
+                },
+                GraphNode::Image("from".to_string()),
+            ],
+            services: Vec::new(),
+        });
+    }
+}
@@ -48,6 +48,11 @@ impl SCellImage {
         &self.blueprint_location
     }
 
+    /// Returns the chain of links, from the entry point target to the root.
+    pub fn chain(&self) -> &[Link] {
+        &self.inner.chain
+    }
+
     pub fn id(&self) -> color_eyre::Result<SCellId> {
         SCellId::new(|hasher| {
             self.hash(hasher)?;
@@ -122,7 +127,8 @@ impl SCellImage {
         })
     }
 
-    fn dump_to_string(&self) -> color_eyre::Result<String> {
+    /// Returns the rendered Dockerfile, the image is built from.
+    pub fn dump_to_string(&self) -> color_eyre::Result<String> {
         let mut dockerfile_str = String::new();
         let mut iter = self.dockerfile.instructions.iter().peekable();
         while let Some(instruction) = iter.next() {
@@ -194,6 +200,34 @@ impl SCellImage {
         tar.append(&header, dockerfile_str.as_bytes())?;
         Ok((tar.into_inner()?.into(), DOCKERFILE_NAME))
     }
+
+    /// Returns the files of the image's build context (including the rendered
+    /// Dockerfile), with their paths inside the context and their sizes.
+    pub fn context_files(&self) -> color_eyre::Result<Vec<(PathBuf, u64)>> {
+        let (tar, _) = self.image_tar_artifact_bytes()?;
+        let mut archive = tar::Archive::new(tar.as_ref());
+        let mut files = Vec::new();
+        for entry in archive.entries()? {
+            let entry = entry?;
+            if entry.header().entry_type().is_file() {
+                files.push((entry.path()?.into_owned(), entry.size()));
+            }
+        }
+        Ok(files)
+    }
+
+    /// Writes the image's build context (including the rendered Dockerfile) into the
+    /// `dir`, so the image could be built with the plain `docker build`.
+    pub fn unpack_context(
+        &self,
+        dir: &Path,
+    ) -> color_eyre::Result<()> {
+        let (tar, _) = self.image_tar_artifact_bytes()?;
+        tar::Archive::new(tar.as_ref()).unpack(dir).context(format!(
+            "Cannot unpack the build context into '{}'",
+            dir.display()
+        ))
+    }
 }
 
 /// Following Docker's `COPY` syntax, the last element in each
@@ -303,3 +337,26 @@ fn prepare_hang_stmt(
 ) {
     dockerfile_instructions.push(Instruction::Entrypoint(vec![hang_stmt.0.clone()]));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scell::SCell;
+
+    #[test]
+    fn context_files_test() {
+        let path = Path::new("src/scell/compile/tests/ok/copy_stmt");
+        let scell = SCell::compile(path, None).unwrap();
+
+        let copy_file: PathBuf = std::fs::canonicalize(path.join("copy_file.txt"))
+            .unwrap()
+            .components()
+            .filter(|c| !matches!(c, std::path::Component::RootDir))
+            .collect();
+        let dockerfile = scell.image().dump_to_string().unwrap();
+        assert_eq!(scell.image().context_files().unwrap(), vec![
+            (copy_file, 0),
+            (PathBuf::from("Dockerfile"), dockerfile.len() as u64),
+        ]);
+    }
+}
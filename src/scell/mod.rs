@@ -7,6 +7,7 @@
 
 mod compile;
 pub mod container;
+pub mod graph;
 pub mod image;
 mod link;
 pub mod name;
@@ -17,6 +18,7 @@ use std::hash::Hash;
 
 use crate::scell::{
     container::SCellContainer,
+    graph::StmtOrigins,
     image::SCellImage,
     link::Link,
     name::SCellId,
@@ -30,6 +32,8 @@ pub struct SCell {
     container: SCellContainer,
     shell: ShellStmt,
     services: Vec<(ServiceName, Service)>,
+    /// The links, the effective statements are taken from.
+    origins: StmtOrigins,
 }
 
 impl SCell {
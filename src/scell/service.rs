@@ -1,7 +1,14 @@
-use crate::scell::{container::SCellContainer, image::SCellImage};
+use crate::scell::{
+    container::SCellContainer, graph::StmtOrigins, image::SCellImage,
+    types::target::services::ServiceName,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Service {
     pub image: SCellImage,
     pub container: SCellContainer,
+    pub origins: StmtOrigins,
+    /// The service, this one is declared in, `None` for the services of the Shell-Cell
+    /// target itself.
+    pub parent: Option<ServiceName>,
 }
@@ -105,11 +105,22 @@
     // ports is a list of port-mapping declarations for the running container.
     // Partially follows Docker Compose short-form syntax.
     // Supported formats (optional "/tcp" or "/udp" suffix, default is tcp):
+    //   "CONTAINER_PORT"                          — random host port
     //   "HOST_PORT:CONTAINER_PORT"                — map a specific host port
     //   "HOST_IP:HOST_PORT:CONTAINER_PORT"        — map with a specific host IP
     //   "HOST_IP::CONTAINER_PORT"                 — random host port on a given IP
-    // Examples: "8080:80", "127.0.0.1:9000:9000", "6060:6060/udp"
+    // Any port could be a range "START-END", container port range must have the same
+    // size as the host port range.
+    // Examples: "8080:80", "127.0.0.1:9000:9000", "6060:6060/udp", "8000-8010:8000-8010"
     ports?: [...string]
+
+    // forward is a list of host facilities, forwarded into the running container.
+    //   "ssh-agent"   — binds the host SSH agent socket ($SSH_AUTH_SOCK) and sets
+    //                   SSH_AUTH_SOCK inside the container
+    //   "gitconfig"   — binds the host ~/.gitconfig as /etc/gitconfig (read-only)
+    //   "known_hosts" — binds the host ~/.ssh/known_hosts as /etc/ssh/ssh_known_hosts
+    //                   (read-only)
+    forward?: [...("ssh-agent" | "gitconfig" | "known_hosts")]
 }
 
 // defining the final contraint 
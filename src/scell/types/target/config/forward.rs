@@ -0,0 +1,111 @@
+use std::{fmt::Display, hash::Hash, str::FromStr};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct ForwardStmt(pub Vec<ForwardItem>);
+
+impl Hash for ForwardStmt {
+    fn hash<H: std::hash::Hasher>(
+        &self,
+        state: &mut H,
+    ) {
+        if !self.0.is_empty() {
+            self.0.hash(state);
+        }
+    }
+}
+
+/// A host facility, forwarded into the running container.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ForwardItem {
+    /// The host SSH agent socket, pointed by the `SSH_AUTH_SOCK` env variable.
+    SshAgent,
+    /// The host user's git configuration file.
+    GitConfig,
+    /// The host user's SSH `known_hosts` file.
+    KnownHosts,
+}
+
+impl ForwardItem {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SshAgent => "ssh-agent",
+            Self::GitConfig => "gitconfig",
+            Self::KnownHosts => "known_hosts",
+        }
+    }
+}
+
+impl Display for ForwardItem {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "forward item must be one of the following: 'ssh-agent', 'gitconfig', 'known_hosts', provided: {0}"
+)]
+pub struct ForwardItemParsingError(String);
+
+impl FromStr for ForwardItem {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ssh-agent" => Ok(Self::SshAgent),
+            "gitconfig" => Ok(Self::GitConfig),
+            "known_hosts" => Ok(Self::KnownHosts),
+            _ => color_eyre::eyre::bail!(ForwardItemParsingError(s.to_string())),
+        }
+    }
+}
+
+impl serde::Serialize for ForwardItem {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ForwardItem {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: serde::Deserializer<'de> {
+        let str = String::deserialize(deserializer)?;
+        str.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    // Success cases
+    #[test_case("ssh-agent" => ForwardItem::SshAgent ; "ssh agent")]
+    #[test_case("gitconfig" => ForwardItem::GitConfig ; "git config")]
+    #[test_case("known_hosts" => ForwardItem::KnownHosts ; "known hosts")]
+    fn test_forward_item_parsing_success(input: &str) -> ForwardItem {
+        ForwardItem::from_str(input).expect("Should parse successfully")
+    }
+
+    // Failure cases
+    #[test_case("" ; "empty string")]
+    #[test_case("ssh_agent" ; "wrong delimiter")]
+    #[test_case("gpg-agent" ; "unsupported item")]
+    fn test_forward_item_parsing_failure(input: &str) {
+        let result = ForwardItem::from_str(input);
+        assert!(
+            result.is_err(),
+            "Input '{input}' should have failed parsing"
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use crate::scell::types::target::config::{
+    forward::ForwardStmt, mounts::MountsStmt, ports::PortsStmt,
+};
+
+pub mod forward;
+pub mod mounts;
+pub mod ports;
+
+#[derive(Debug, Clone, PartialEq, Default, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub struct ConfigStmt {
+    #[serde(default)]
+    pub mounts: MountsStmt,
+    #[serde(default)]
+    pub ports: PortsStmt,
+    #[serde(default)]
+    pub forward: ForwardStmt,
+}
@@ -0,0 +1,362 @@
+use std::{fmt::Display, hash::Hash, str::FromStr};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct PortsStmt(pub Vec<PortItem>);
+
+impl Hash for PortsStmt {
+    fn hash<H: std::hash::Hasher>(
+        &self,
+        state: &mut H,
+    ) {
+        if !self.0.is_empty() {
+            self.0.hash(state);
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PortsStmt {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: serde::Deserializer<'de> {
+        Ok(Self(Vec::deserialize(deserializer)?))
+    }
+}
+
+/// The network protocol for a port binding.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, serde::Serialize)]
+pub enum PortProtocol {
+    #[default]
+    Tcp,
+    Udp,
+}
+
+impl PortProtocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tcp => "tcp",
+            Self::Udp => "udp",
+        }
+    }
+}
+
+/// A single port or an inclusive range of ports, e.g. `8080` or `8000-8010`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PortRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+impl PortRange {
+    /// Returns the amount of ports covered by the range.
+    pub fn len(self) -> u16 {
+        self.end.saturating_sub(self.start).saturating_add(1)
+    }
+
+    /// Returns an iterator over every port covered by the range.
+    pub fn ports(self) -> impl Iterator<Item = u16> {
+        self.start..=self.end
+    }
+}
+
+impl Display for PortRange {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        if self.start == self.end {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
+impl FromStr for PortRange {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('-').unwrap_or((s, s));
+        let start: u16 = start.parse()?;
+        let end: u16 = end.parse()?;
+        color_eyre::eyre::ensure!(
+            start <= end,
+            "port range start must not be greater than its end, provided: {s}"
+        );
+        Ok(Self { start, end })
+    }
+}
+
+impl serde::Serialize for PortRange {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// A single port mapping in short form, matching Docker Compose short syntax.
+///
+/// Supported formats:
+/// - `CONTAINER_PORT`
+/// - `HOST_PORT:CONTAINER_PORT`
+/// - `HOST_IP:HOST_PORT:CONTAINER_PORT`
+/// - `HOST_IP::CONTAINER_PORT`
+/// - Any of the above with port ranges (e.g. `8000-8010:8000-8010`)
+/// - Any of the above with `/tcp` or `/udp` suffix
+///
+/// When the host port is omitted, Docker picks a random available host port.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct PortItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_ip: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_port: Option<PortRange>,
+    pub container_port: PortRange,
+    pub protocol: PortProtocol,
+}
+
+impl Hash for PortItem {
+    fn hash<H: std::hash::Hasher>(
+        &self,
+        state: &mut H,
+    ) {
+        // Hashed as the port strings, the items were stored with before the port ranges
+        // support, so the containers of the existing blueprints keep their IDs
+        self.host_ip.hash(state);
+        self.host_port
+            .map(|p| p.to_string())
+            .unwrap_or_default()
+            .hash(state);
+        self.container_port.to_string().hash(state);
+        self.protocol.hash(state);
+    }
+}
+
+impl PortItem {
+    /// Expands the item into the list of `(container_port, host_port)` pairs, one per
+    /// container port.
+    ///
+    /// A container port range is mapped one-to-one onto the host port range.
+    /// A single container port keeps the whole host port range, so Docker could pick any
+    /// available host port from it.
+    pub fn expand(&self) -> Vec<(u16, Option<String>)> {
+        match self.host_port {
+            Some(host_port) if self.container_port.len() > 1 => {
+                self.container_port
+                    .ports()
+                    .zip(host_port.ports())
+                    .map(|(c, h)| (c, Some(h.to_string())))
+                    .collect()
+            },
+            host_port => {
+                self.container_port
+                    .ports()
+                    .map(|c| (c, host_port.map(|h| h.to_string())))
+                    .collect()
+            },
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "port item must be in the format '[HOST_IP:][HOST_PORT:]CONTAINER_PORT[/PROTOCOL]', provided: {0}"
+)]
+pub struct PortItemParsingError(String);
+
+impl FromStr for PortItem {
+    type Err = color_eyre::eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Split off optional /protocol suffix
+        let (port_spec, protocol) = match s.rsplit_once('/') {
+            Some((spec, "tcp")) => (spec, PortProtocol::Tcp),
+            Some((spec, "udp")) => (spec, PortProtocol::Udp),
+            Some(_) => color_eyre::eyre::bail!(PortItemParsingError(s.to_string())),
+            None => (s, PortProtocol::Tcp),
+        };
+
+        let parse_range = |v: &str| {
+            v.parse::<PortRange>()
+                .map_err(|_| PortItemParsingError(s.to_string()))
+        };
+
+        // Split into at most 3 parts on ':'.
+        // Examples:
+        //   "80"                  → ["80"]
+        //   "8080:80"             → ["8080", "80"]
+        //   "127.0.0.1:8001:8001" → ["127.0.0.1", "8001", "8001"]
+        //   "127.0.0.1::8001"     → ["127.0.0.1", "", "8001"]
+        let parts: Vec<&str> = port_spec.splitn(3, ':').collect();
+        let (host_ip, host_port, container_port) = match parts.as_slice() {
+            [container_port] => (None, None, parse_range(container_port)?),
+            [host_port, container_port] => {
+                (
+                    None,
+                    Some(parse_range(host_port)?),
+                    parse_range(container_port)?,
+                )
+            },
+            [host_ip, host_port, container_port] if !host_ip.is_empty() => {
+                let host_port = (!host_port.is_empty())
+                    .then(|| parse_range(host_port))
+                    .transpose()?;
+                (
+                    Some(host_ip.to_string()),
+                    host_port,
+                    parse_range(container_port)?,
+                )
+            },
+            _ => color_eyre::eyre::bail!(PortItemParsingError(s.to_string())),
+        };
+
+        // A container port range must be mapped onto the host port range of the same size
+        if let Some(host_port) = host_port
+            && container_port.len() > 1
+        {
+            color_eyre::eyre::ensure!(
+                host_port.len() == container_port.len(),
+                PortItemParsingError(s.to_string())
+            );
+        }
+
+        Ok(Self {
+            host_ip,
+            host_port,
+            container_port,
+            protocol,
+        })
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PortItem {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: serde::Deserializer<'de> {
+        // Accept both quoted strings ("8080:80") and bare integers (3000) from YAML.
+        struct PortItemVisitor;
+
+        impl serde::de::Visitor<'_> for PortItemVisitor {
+            type Value = PortItem;
+
+            fn expecting(
+                &self,
+                f: &mut std::fmt::Formatter<'_>,
+            ) -> std::fmt::Result {
+                write!(
+                    f,
+                    "a port mapping string like '8080:80' or a bare port number like 3000"
+                )
+            }
+
+            fn visit_str<E: serde::de::Error>(
+                self,
+                v: &str,
+            ) -> Result<PortItem, E> {
+                v.parse().map_err(serde::de::Error::custom)
+            }
+
+            fn visit_u64<E: serde::de::Error>(
+                self,
+                v: u64,
+            ) -> Result<PortItem, E> {
+                v.to_string().parse().map_err(serde::de::Error::custom)
+            }
+
+            fn visit_i64<E: serde::de::Error>(
+                self,
+                v: i64,
+            ) -> Result<PortItem, E> {
+                v.to_string().parse().map_err(serde::de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_any(PortItemVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    // Success cases
+    #[test_case("8080:80" => PortItem { host_ip: None, host_port: Some(PortRange { start: 8080, end: 8080 }), container_port: PortRange { start: 80, end: 80 }, protocol: PortProtocol::Tcp } ; "host and container port")]
+    #[test_case("127.0.0.1:8001:8001" => PortItem { host_ip: Some("127.0.0.1".to_string()), host_port: Some(PortRange { start: 8001, end: 8001 }), container_port: PortRange { start: 8001, end: 8001 }, protocol: PortProtocol::Tcp } ; "ip host and container port")]
+    #[test_case("6060:6060/udp" => PortItem { host_ip: None, host_port: Some(PortRange { start: 6060, end: 6060 }), container_port: PortRange { start: 6060, end: 6060 }, protocol: PortProtocol::Udp } ; "udp protocol")]
+    #[test_case("3000" => PortItem { host_ip: None, host_port: None, container_port: PortRange { start: 3000, end: 3000 }, protocol: PortProtocol::Tcp } ; "container port only")]
+    #[test_case("127.0.0.1::8001" => PortItem { host_ip: Some("127.0.0.1".to_string()), host_port: None, container_port: PortRange { start: 8001, end: 8001 }, protocol: PortProtocol::Tcp } ; "ip with random host port")]
+    #[test_case("8000-8010:8000-8010" => PortItem { host_ip: None, host_port: Some(PortRange { start: 8000, end: 8010 }), container_port: PortRange { start: 8000, end: 8010 }, protocol: PortProtocol::Tcp } ; "port ranges")]
+    #[test_case("9000-9010:80" => PortItem { host_ip: None, host_port: Some(PortRange { start: 9000, end: 9010 }), container_port: PortRange { start: 80, end: 80 }, protocol: PortProtocol::Tcp } ; "host port range with single container port")]
+    fn test_port_item_parsing_success(input: &str) -> PortItem {
+        PortItem::from_str(input).expect("Should parse successfully")
+    }
+
+    // Failure cases
+    #[test_case("" ; "empty string")]
+    #[test_case(":80" ; "empty host port with no ip")]
+    #[test_case("8080:80/ftp" ; "unsupported protocol")]
+    #[test_case("http:80" ; "non numeric host port")]
+    #[test_case("8010-8000:80" ; "reversed port range")]
+    #[test_case("8000-8005:8000-8010" ; "mismatched port ranges")]
+    #[test_case("70000:80" ; "port out of range")]
+    fn test_port_item_parsing_failure(input: &str) {
+        let result = PortItem::from_str(input);
+        assert!(
+            result.is_err(),
+            "Input '{input}' should have failed parsing"
+        );
+    }
+
+    #[test_case("8080:80" => vec![(80, Some("8080".to_string()))] ; "single port")]
+    #[test_case("80" => vec![(80, None)] ; "random host port")]
+    #[test_case("8000-8002:9000-9002" => vec![
+        (9000, Some("8000".to_string())),
+        (9001, Some("8001".to_string())),
+        (9002, Some("8002".to_string())),
+    ] ; "port ranges")]
+    #[test_case("9000-9010:80" => vec![(80, Some("9000-9010".to_string()))] ; "host port range")]
+    #[test_case("127.0.0.1::8000-8001" => vec![(8000, None), (8001, None)] ; "container port range with random host ports")]
+    fn test_port_item_expand(input: &str) -> Vec<(u16, Option<String>)> {
+        PortItem::from_str(input)
+            .expect("Should parse successfully")
+            .expand()
+    }
+
+    #[test_case("8080:80", None, "8080", "80", PortProtocol::Tcp ; "host and container port")]
+    #[test_case("127.0.0.1:8001:8001/udp", Some("127.0.0.1"), "8001", "8001", PortProtocol::Udp ; "ip host and container port, udp")]
+    fn test_port_item_hash_is_stable(
+        input: &str,
+        host_ip: Option<&str>,
+        host_port: &str,
+        container_port: &str,
+        protocol: PortProtocol,
+    ) {
+        /// The port item, as it was stored before the port ranges support.
+        #[derive(Hash)]
+        struct LegacyPortItem {
+            host_ip: Option<String>,
+            host_port: String,
+            container_port: String,
+            protocol: PortProtocol,
+        }
+
+        fn hash(value: &impl Hash) -> u64 {
+            let mut hasher = std::hash::DefaultHasher::new();
+            value.hash(&mut hasher);
+            std::hash::Hasher::finish(&hasher)
+        }
+
+        let item: PortItem = input.parse().unwrap();
+        let legacy = LegacyPortItem {
+            host_ip: host_ip.map(ToString::to_string),
+            host_port: host_port.to_string(),
+            container_port: container_port.to_string(),
+            protocol,
+        };
+        assert_eq!(hash(&item), hash(&legacy));
+    }
+}
@@ -0,0 +1,2 @@
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+pub struct HangStmt(pub String);